@@ -8,29 +8,58 @@
 //! * unix timestamps, e.g., "@12"
 //! * relative time to now, e.g. "+1 hour"
 //!
-use regex::Error as RegexError;
+use regex::{Error as RegexError, Regex};
 use std::error::Error;
 use std::fmt::{self, Display};
+use std::sync::LazyLock;
 
 // Expose parse_datetime
+mod parse_business_day;
+mod parse_day_of_month;
+mod parse_day_time;
+mod parse_daypart;
+mod parse_ordinal;
+mod parse_recurrence;
 mod parse_relative_time;
+mod parse_rfc2822;
 mod parse_timestamp;
 
 mod parse_time_only_str;
 mod parse_weekday;
+mod parse_weekend;
 
 use chrono::{
-    DateTime, Datelike, Duration, FixedOffset, Local, LocalResult, NaiveDateTime, TimeZone,
-    Timelike,
+    DateTime, Datelike, Duration, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime,
+    NaiveTime, TimeZone, Timelike, Utc, Weekday,
 };
 
 use parse_relative_time::parse_relative_time_at_date;
-use parse_timestamp::parse_timestamp;
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParseDateTimeError {
-    InvalidRegex(RegexError),
+    InvalidRegex(#[cfg_attr(feature = "serde", serde(with = "regex_error"))] RegexError),
     InvalidInput,
+    NegativeDuration,
+    AmbiguousCalendarUnit,
+}
+
+/// `regex::Error` isn't serializable, so it is (de)serialized as its message.
+#[cfg(feature = "serde")]
+mod regex_error {
+    use regex::Error as RegexError;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(err: &RegexError, s: S) -> Result<S::Ok, S::Error> {
+        match err {
+            RegexError::Syntax(msg) => s.serialize_str(msg),
+            err => s.collect_str(err),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<RegexError, D::Error> {
+        String::deserialize(d).map(RegexError::Syntax)
+    }
 }
 
 impl Display for ParseDateTimeError {
@@ -45,6 +74,15 @@ impl Display for ParseDateTimeError {
                     "Invalid input string: cannot be parsed as a relative time"
                 )
             }
+            Self::NegativeDuration => {
+                write!(f, "Invalid duration: duration cannot be negative")
+            }
+            Self::AmbiguousCalendarUnit => {
+                write!(
+                    f,
+                    "Invalid duration: years and months do not have a fixed length"
+                )
+            }
         }
     }
 }
@@ -57,11 +95,31 @@ impl From<RegexError> for ParseDateTimeError {
     }
 }
 
+/// The outcome of [`parse_or_identity`].
+#[derive(Debug, PartialEq)]
+pub enum ParseOutcome<'a> {
+    /// The input was parsed as a time.
+    Parsed(DateTime<FixedOffset>),
+    /// The input could not be parsed and is returned unchanged.
+    Unchanged(&'a str),
+}
+
+/// The first day of the week for [`parse_datetime_at_date_with_week_start`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeekStart {
+    /// Weeks start on Monday, as in ISO 8601.
+    Monday,
+    /// Weeks start on Sunday, as in the US.
+    Sunday,
+}
+
 /// Formats that parse input can take.
 /// Taken from `touch` coreutils
 mod format {
     pub const ISO_8601: &str = "%Y-%m-%d";
     pub const ISO_8601_NO_SEP: &str = "%Y%m%d";
+    pub const ISO_8601_WEEK: &str = "%G-W%V-%u";
+    pub const ISO_8601_ORDINAL: &str = "%Y-%j";
     pub const POSIX_LOCALE: &str = "%a %b %e %H:%M:%S %Y";
     pub const YYYYMMDDHHMM_DOT_SS: &str = "%Y%m%d%H%M.%S";
     pub const YYYYMMDDHHMMSS: &str = "%Y-%m-%d %H:%M:%S.%f";
@@ -73,6 +131,7 @@ mod format {
     pub const YYYYMMDDHHMM_ZULU_OFFSET: &str = "%Y%m%d%H%MZ%z";
     pub const YYYYMMDDHHMM_HYPHENATED_OFFSET: &str = "%Y-%m-%d %H:%M %z";
     pub const YYYYMMDDHHMMS_T_SEP: &str = "%Y-%m-%dT%H:%M:%S";
+    pub const YYYYMMDDTHHMMSS_BASIC_OFFSET: &str = "%Y%m%dT%H%M%S%.f%#z";
     pub const UTC_OFFSET: &str = "UTC%#z";
     pub const ZULU_OFFSET: &str = "Z%#z";
 }
@@ -143,10 +202,72 @@ pub fn parse_datetime<S: AsRef<str> + Clone>(
 pub fn parse_datetime_at_date<S: AsRef<str> + Clone>(
     date: DateTime<Local>,
     s: S,
+) -> Result<DateTime<FixedOffset>, ParseDateTimeError> {
+    parse_datetime_at_date_inner(date, s, None).map(roll_over_leap_second)
+}
+
+/// Parses a time string at a specific date like [`parse_datetime_at_date`],
+/// but resolves "this" and "next", "last" or an ordinal in front of a weekday
+/// within weeks that start on `week_start`.
+///
+/// "this sunday" is the Sunday of the current week, which may be in the past,
+/// and "next sunday" is the Sunday of the following week. A plain weekday
+/// still moves forward to its next occurrence.
+///
+/// # Arguments
+///
+/// * date - The date represented in local time
+/// * `s` - A string slice representing the time.
+/// * `week_start` - The first day of the week.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Local, TimeZone};
+/// use parse_datetime::{parse_datetime_at_date_with_week_start, WeekStart};
+///
+/// // 2024-03-13 is a Wednesday
+/// let date = Local.with_ymd_and_hms(2024, 3, 13, 10, 0, 0).unwrap();
+///
+/// let sunday = parse_datetime_at_date_with_week_start(date, "this sunday", WeekStart::Monday);
+/// assert_eq!(sunday.unwrap().format("%F").to_string(), "2024-03-17");
+///
+/// let sunday = parse_datetime_at_date_with_week_start(date, "this sunday", WeekStart::Sunday);
+/// assert_eq!(sunday.unwrap().format("%F").to_string(), "2024-03-10");
+/// ```
+///
+/// # Returns
+///
+/// * `Ok(DateTime<FixedOffset>)` - If the input string can be parsed as a time
+/// * `Err(ParseDateTimeError)` - If the input string cannot be parsed as a date/time
+///
+/// # Errors
+///
+/// This function will return `Err(ParseDateTimeError::InvalidInput)` if the input string
+/// cannot be parsed as a date/time.
+pub fn parse_datetime_at_date_with_week_start<S: AsRef<str> + Clone>(
+    date: DateTime<Local>,
+    s: S,
+    week_start: WeekStart,
+) -> Result<DateTime<FixedOffset>, ParseDateTimeError> {
+    parse_datetime_at_date_inner(date, s, Some(week_start)).map(roll_over_leap_second)
+}
+
+fn parse_datetime_at_date_inner<S: AsRef<str> + Clone>(
+    date: DateTime<Local>,
+    s: S,
+    week_start: Option<WeekStart>,
 ) -> Result<DateTime<FixedOffset>, ParseDateTimeError> {
     // TODO: Replace with a proper customiseable parsing solution using `nom`, `grmtools`, or
     // similar
 
+    // Like gnu date, an empty or whitespace-only string is the start of today
+    if s.as_ref().trim().is_empty() {
+        if let Ok(dt) = naive_dt_to_fixed_offset(date, date.date_naive().and_time(NaiveTime::MIN)) {
+            return Ok(dt);
+        }
+    }
+
     // Formats with offsets don't require NaiveDateTime workaround
     for fmt in [
         format::YYYYMMDDHHMM_OFFSET,
@@ -159,37 +280,66 @@ pub fn parse_datetime_at_date<S: AsRef<str> + Clone>(
         }
     }
 
+    // Parse RFC 2822 dates, e.g. from the "Date:" header of an email
+    if let Some(parsed) = parse_rfc2822::parse_rfc2822(s.as_ref()) {
+        return Ok(parsed);
+    }
+
+    // ISO 8601 basic format, which may use a comma as the decimal sign of the
+    // fractional seconds
+    if let Ok(parsed) = DateTime::parse_from_str(
+        &s.as_ref().replacen(',', ".", 1),
+        format::YYYYMMDDTHHMMSS_BASIC_OFFSET,
+    ) {
+        return Ok(parsed);
+    }
+
     // Parse formats with no offset, assume local time
-    for fmt in [
-        format::YYYYMMDDHHMMS_T_SEP,
-        format::YYYYMMDDHHMM,
-        format::YYYYMMDDHHMMS,
-        format::YYYYMMDDHHMMSS,
-        format::YYYY_MM_DD_HH_MM,
-        format::YYYYMMDDHHMM_DOT_SS,
-        format::POSIX_LOCALE,
-    ] {
-        if let Ok(parsed) = NaiveDateTime::parse_from_str(s.as_ref(), fmt) {
-            if let Ok(dt) = naive_dt_to_fixed_offset(date, parsed) {
-                return Ok(dt);
+    if let Some(parsed) = parse_naive_date_time(s.as_ref()) {
+        if let Ok(dt) = naive_dt_to_fixed_offset(date, parsed) {
+            return Ok(dt);
+        }
+    }
+
+    // parse weekday, optionally with "next", "last", "this" or an ordinal such
+    // as "third", and a time
+    if let Some(dt) = parse_weekday_at_date(date, s.as_ref(), week_start) {
+        return Ok(dt);
+    }
+
+    // Parse a weekday or a time keyword combined with a relative time in either
+    // order, e.g. "monday + 2 days", "+2 days next monday 14:00" or "noon
+    // tomorrow". The order doesn't matter: the weekday or keyword is resolved
+    // first and the relative time applied to it. Splitting is only tried if
+    // one of the words is a weekday or a keyword
+    static SIGN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([+-])\s+").unwrap());
+    let input = s.as_ref().trim();
+    let has_anchor = input.split_whitespace().any(|word| {
+        parse_weekday::is_weekday(word) || parse_time_only_str::keyword_to_time(word).is_some()
+    });
+    if has_anchor {
+        for (i, _) in input.match_indices(char::is_whitespace) {
+            let (left, right) = (input[..i].trim(), input[i..].trim());
+            for (anchor, relative) in [(left, right), (right, left)] {
+                let anchor = parse_weekday_at_date(date, anchor, week_start).or_else(|| {
+                    let time = parse_time_only_str::keyword_to_time(anchor)?;
+                    naive_dt_to_fixed_offset(date, date.date_naive().and_time(time)).ok()
+                });
+                if let Some(dt) = anchor {
+                    if let Ok(dt) = parse_relative_time_at_date(dt, &SIGN.replace(relative, "$1")) {
+                        return Ok(dt);
+                    }
+                }
             }
         }
     }
 
-    // parse weekday
-    if let Some(weekday) = parse_weekday::parse_weekday(s.as_ref()) {
-        let mut beginning_of_day = date
-            .with_hour(0)
-            .unwrap()
-            .with_minute(0)
-            .unwrap()
-            .with_second(0)
-            .unwrap()
-            .with_nanosecond(0)
-            .unwrap();
+    // parse next or previous business day
+    if let Some(step) = parse_business_day::parse_business_day(s.as_ref()) {
+        let mut beginning_of_day = beginning_of_day(date) + Duration::days(step);
 
-        while beginning_of_day.weekday() != weekday {
-            beginning_of_day += Duration::days(1);
+        while !parse_business_day::is_business_day(beginning_of_day.weekday()) {
+            beginning_of_day += Duration::days(step);
         }
 
         let dt = DateTime::<FixedOffset>::from(beginning_of_day);
@@ -197,29 +347,45 @@ pub fn parse_datetime_at_date<S: AsRef<str> + Clone>(
         return Ok(dt);
     }
 
+    // parse a number of business hours, e.g. "+4 business hours"
+    if let Some(hours) = parse_business_day::parse_business_hours(s.as_ref()) {
+        if let Some(dt) = parse_business_day::add_business_hours(date.naive_local(), hours) {
+            if let Ok(dt) = naive_dt_to_fixed_offset(date, dt) {
+                return Ok(dt);
+            }
+        }
+    }
+
+    // parse next, last or this weekend
+    if let Some(direction) = parse_weekend::parse_weekend(s.as_ref()) {
+        let days = parse_weekend::days_to_weekend(direction, date.weekday());
+        let dt = DateTime::<FixedOffset>::from(beginning_of_day(date) + Duration::days(days));
+
+        return Ok(dt);
+    }
+
     // Parse epoch seconds
-    if let Ok(timestamp) = parse_timestamp(s.as_ref()) {
-        if let Some(timestamp_date) = DateTime::from_timestamp(timestamp, 0) {
+    if let Ok((timestamp, nanos)) = parse_timestamp::parse_timestamp(s.as_ref()) {
+        if let Some(timestamp_date) = DateTime::from_timestamp(timestamp, nanos) {
             return Ok(timestamp_date.into());
         }
     }
 
-    let ts = s.as_ref().to_owned() + " 0000";
     // Parse date only formats - assume midnight local timezone
-    for fmt in [format::ISO_8601, format::ISO_8601_NO_SEP] {
-        let f = fmt.to_owned() + " %H%M";
-        if let Ok(parsed) = NaiveDateTime::parse_from_str(&ts, &f) {
-            if let Ok(dt) = naive_dt_to_fixed_offset(date, parsed) {
-                return Ok(dt);
-            }
+    if let Some(parsed) = parse_date_only(date, s.as_ref()) {
+        if let Ok(dt) = naive_dt_to_fixed_offset(date, parsed.and_time(NaiveTime::MIN)) {
+            return Ok(dt);
         }
     }
 
     // Parse offsets. chrono doesn't provide any functionality to parse
     // offsets, so instead we replicate parse_date behaviour by getting
     // the current date with local, and create a date time string at midnight,
-    // before trying offset suffixes
-    let ts = format!("{}", date.format("%Y%m%d")) + "0000" + s.as_ref();
+    // before trying offset suffixes. chrono also requires the hour of an
+    // offset, so an omitted hour as in "UTC+:30" is filled in with zero
+    let offset =
+        Regex::new(r"^(?<prefix>UTC|Z)(?<sign>[+-]):")?.replace(s.as_ref(), "${prefix}${sign}00:");
+    let ts = format!("{}", date.format("%Y%m%d")) + "0000" + &offset;
     for fmt in [format::UTC_OFFSET, format::ZULU_OFFSET] {
         let f = format::YYYYMMDDHHMM.to_owned() + fmt;
         if let Ok(parsed) = DateTime::parse_from_str(&ts, &f) {
@@ -227,6 +393,59 @@ pub fn parse_datetime_at_date<S: AsRef<str> + Clone>(
         }
     }
 
+    // Parse a trailing time zone abbreviation, e.g. "10:00 PST", by replacing
+    // it with its numeric offset
+    let abbreviation = Regex::new(r"^(?<rest>.*\S)\s+(?<tz>[[:upper:]]{2,4})$")?;
+    if let Some(captures) = abbreviation.captures(s.as_ref()) {
+        if let Some(offset) = parse_time_only_str::abbreviation_to_offset(&captures["tz"]) {
+            let ts = format!(
+                "{} {}",
+                &captures["rest"],
+                offset.to_string().replace(':', "")
+            );
+            return parse_datetime_at_date_inner(date, ts, week_start);
+        }
+    }
+
+    // Parse a part of the day, e.g. "this morning" or "tomorrow evening"
+    if let Some((days, time)) = parse_daypart::parse_daypart(s.as_ref()) {
+        let day = date.date_naive() + Duration::days(days);
+        if let Ok(dt) = naive_dt_to_fixed_offset(date, day.and_time(time)) {
+            return Ok(dt);
+        }
+    }
+
+    // Parse a day followed by a time, e.g. "tomorrow at 10:00"
+    if let Some((days, time)) = parse_day_time::parse_day_time(s.as_ref()) {
+        let day = beginning_of_day(date) + Duration::days(days.unwrap_or(0));
+        if let Some(date_time) = parse_time_only_str::parse_time_only(day, time) {
+            return match days {
+                Some(_) => Ok(date_time),
+                // "now" already implies the current time of day
+                None => Err(ParseDateTimeError::InvalidInput),
+            };
+        }
+    }
+
+    // Parse a relative time applied to another time, e.g. "2 hours after midnight"
+    // or "the day before 2022-11-14"
+    let re = Regex::new(
+        r"^\s*(?i:the\s+)?(?<relative>.+?)\s+(?<connector>(?i:before|after))\s+(?<anchor>.+)$",
+    )?;
+    if let Some(captures) = re.captures(s.as_ref()) {
+        if let Ok(anchor) = parse_datetime_at_date_inner(date, &captures["anchor"], week_start)
+            .map(roll_over_leap_second)
+        {
+            let relative = match captures["connector"].to_lowercase().as_str() {
+                "before" => format!("{} ago", &captures["relative"]),
+                _ => captures["relative"].to_owned(),
+            };
+            if let Ok(dt) = parse_relative_time_at_date(anchor, &relative) {
+                return Ok(dt);
+            }
+        }
+    }
+
     // Parse relative time.
     if let Ok(datetime) = parse_relative_time_at_date(date, s.as_ref()) {
         return Ok(DateTime::<FixedOffset>::from(datetime));
@@ -237,55 +456,594 @@ pub fn parse_datetime_at_date<S: AsRef<str> + Clone>(
         return Ok(date_time);
     }
 
+    // Parse a date or time followed by a relative time, e.g. "2021-02-15
+    // 06:37:47 UTC +1 day". The relative time is the longest trailing part
+    // that parses as one, and it is applied in the offset of the date or time
+    for (i, _) in input.match_indices(char::is_whitespace) {
+        let relative = SIGN.replace(input[i..].trim(), "$1");
+        if parse_relative_time_at_date(date, &relative).is_ok() {
+            if let Ok(anchor) = parse_datetime_at_date_inner(date, input[..i].trim(), week_start)
+                .map(roll_over_leap_second)
+            {
+                if let Ok(dt) = parse_relative_time_at_date(anchor, &relative) {
+                    return Ok(dt);
+                }
+            }
+            break;
+        }
+    }
+
     // Default parse and failure
     s.as_ref()
         .parse()
-        .map_err(|_| (ParseDateTimeError::InvalidInput))
+        .map_err(|_| ParseDateTimeError::InvalidInput)
 }
 
-// Convert NaiveDateTime to DateTime<FixedOffset> by assuming the offset
-// is local time
-fn naive_dt_to_fixed_offset(
-    local: DateTime<Local>,
-    dt: NaiveDateTime,
-) -> Result<DateTime<FixedOffset>, ()> {
-    match local.offset().from_local_datetime(&dt) {
-        LocalResult::Single(dt) => Ok(dt),
-        _ => Err(()),
+/// Parses a relative time string and returns the `std::time::Duration` that
+/// it represents.
+///
+/// Only units with a fixed length are accepted, so years, quarters and months
+/// are rejected.
+///
+/// # Arguments
+///
+/// * `s` - A string slice representing the relative time.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use parse_datetime::parse_std_duration;
+///
+/// assert_eq!(parse_std_duration("1h30m"), Ok(Duration::from_secs(5400)));
+/// ```
+///
+/// # Returns
+///
+/// * `Ok(Duration)` - If the input string can be parsed as a non-negative duration
+/// * `Err(ParseDateTimeError)` - If the input string cannot be parsed as a duration
+///
+/// # Errors
+///
+/// This function will return
+/// * `Err(ParseDateTimeError::InvalidInput)` if the input string cannot be parsed as a
+///   relative time.
+/// * `Err(ParseDateTimeError::AmbiguousCalendarUnit)` if the input string contains years,
+///   quarters or months.
+/// * `Err(ParseDateTimeError::NegativeDuration)` if the resulting duration is negative.
+pub fn parse_std_duration<S: AsRef<str>>(s: S) -> Result<std::time::Duration, ParseDateTimeError> {
+    parse_relative_time::parse_std_duration(s.as_ref())
+}
+
+/// Parses a time string, or returns the input unchanged if it cannot be parsed.
+///
+/// This is useful for templating, where dates are interpolated and anything
+/// else is kept literally.
+///
+/// # Arguments
+///
+/// * `s` - A string slice representing the time.
+///
+/// # Examples
+///
+/// ```
+/// use parse_datetime::{parse_or_identity, ParseOutcome};
+///
+/// assert!(matches!(parse_or_identity("@0"), ParseOutcome::Parsed(_)));
+/// assert_eq!(parse_or_identity("foobar"), ParseOutcome::Unchanged("foobar"));
+/// ```
+pub fn parse_or_identity(s: &str) -> ParseOutcome<'_> {
+    match parse_datetime(s) {
+        Ok(dt) => ParseOutcome::Parsed(dt),
+        Err(_) => ParseOutcome::Unchanged(s),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    static TEST_TIME: i64 = 1613371067;
+/// Parses a relative time string and applies it to a `NaiveDateTime`, without
+/// any timezone involvement.
+///
+/// Only relative times are accepted, absolute dates and times are rejected.
+///
+/// # Arguments
+///
+/// * `base` - The naive date and time to apply the relative time to
+/// * `s` - A string slice representing the relative time.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use parse_datetime::apply_relative;
+///
+/// let base = NaiveDate::from_ymd_opt(2024, 3, 15)
+///     .unwrap()
+///     .and_hms_opt(10, 0, 0)
+///     .unwrap();
+/// assert_eq!(
+///     apply_relative(base, "+1 day 2 hours").unwrap(),
+///     NaiveDate::from_ymd_opt(2024, 3, 16)
+///         .unwrap()
+///         .and_hms_opt(12, 0, 0)
+///         .unwrap()
+/// );
+/// ```
+///
+/// # Returns
+///
+/// * `Ok(NaiveDateTime)` - If the input string can be parsed as a relative time
+/// * `Err(ParseDateTimeError)` - If the input string cannot be parsed as a relative time
+///
+/// # Errors
+///
+/// This function will return `Err(ParseDateTimeError::InvalidInput)` if the input string
+/// cannot be parsed as a relative time.
+pub fn apply_relative<S: AsRef<str>>(
+    base: NaiveDateTime,
+    s: S,
+) -> Result<NaiveDateTime, ParseDateTimeError> {
+    // UTC has no DST transitions, so this is plain calendar arithmetic
+    parse_relative_time_at_date(Utc.from_utc_datetime(&base), s.as_ref()).map(|dt| dt.naive_utc())
+}
 
-    #[cfg(test)]
-    mod iso_8601 {
-        use std::env;
+// The largest count accepted by `recurrence`
+const MAX_RECURRENCE_COUNT: usize = 10_000;
 
-        use crate::ParseDateTimeError;
-        use crate::{parse_datetime, tests::TEST_TIME};
+/// Parses a recurrence such as "every 2 weeks" and returns the first `count`
+/// occurrences after `base`.
+///
+/// The n-th occurrence is `base` plus n times the relative time, so month
+/// and year steps roll over the same way as in a single relative time
+/// without drifting from one occurrence to the next. Absolute dates and times
+/// are rejected.
+///
+/// At most 10,000 occurrences are returned, so that a caller-controlled
+/// count can't allocate without bound. A larger count is rejected.
+///
+/// # Arguments
+///
+/// * `base` - The date and time the recurrence starts from, which is not
+///   itself an occurrence.
+/// * `s` - A string slice of the form "every <relative time>".
+/// * `count` - The number of occurrences to return, up to 10,000.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use parse_datetime::recurrence;
+///
+/// let base = Utc.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+/// assert_eq!(
+///     recurrence(base, "every 2 weeks", 2).unwrap(),
+///     vec![base + Duration::weeks(2), base + Duration::weeks(4)]
+/// );
+/// ```
+///
+/// # Returns
+///
+/// * `Ok(Vec<DateTime<T>>)` - If the input string can be parsed as a recurrence
+/// * `Err(ParseDateTimeError)` - If the input string cannot be parsed as a recurrence
+///
+/// # Errors
+///
+/// This function will return `Err(ParseDateTimeError::InvalidInput)` if the input string
+/// cannot be parsed as a recurrence, if `count` is more than 10,000 or if an occurrence is
+/// out of range.
+pub fn recurrence<T: TimeZone, S: AsRef<str>>(
+    base: DateTime<T>,
+    s: S,
+    count: usize,
+) -> Result<Vec<DateTime<T>>, ParseDateTimeError> {
+    if count > MAX_RECURRENCE_COUNT {
+        return Err(ParseDateTimeError::InvalidInput);
+    }
+    let relative =
+        parse_recurrence::parse_recurrence(s.as_ref()).ok_or(ParseDateTimeError::InvalidInput)?;
+
+    // Each occurrence is computed from the base, so that "every month" from
+    // January 31 doesn't drift to the 28th after February
+    (1..=count)
+        .map(|n| {
+            let n = i64::try_from(n).map_err(|_| ParseDateTimeError::InvalidInput)?;
+            parse_relative_time::parse_relative_time_times_at_date(base.clone(), relative, n)
+        })
+        .collect()
+}
 
-        #[test]
-        fn test_t_sep() {
-            env::set_var("TZ", "UTC");
-            let dt = "2021-02-15T06:37:47";
-            let actual = parse_datetime(dt);
-            assert_eq!(actual.unwrap().timestamp(), TEST_TIME);
-        }
+/// Parses a time string and returns the wall-clock `NaiveDateTime` and the
+/// `FixedOffset` it was resolved to as separate values.
+///
+/// This is useful when the wall-clock time and the offset need to be
+/// displayed or stored independently of each other.
+///
+/// # Arguments
+///
+/// * `s` - A string slice representing the time.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{FixedOffset, NaiveDate};
+/// use parse_datetime::parse_datetime_split;
+///
+/// let (naive, offset) = parse_datetime_split("2021-02-14 22:37:47 -0800").unwrap();
+/// assert_eq!(
+///     naive,
+///     NaiveDate::from_ymd_opt(2021, 2, 14)
+///         .unwrap()
+///         .and_hms_opt(22, 37, 47)
+///         .unwrap()
+/// );
+/// assert_eq!(offset, FixedOffset::west_opt(8 * 3600).unwrap());
+/// ```
+///
+/// # Returns
+///
+/// * `Ok((NaiveDateTime, FixedOffset))` - If the input string can be parsed as a time
+/// * `Err(ParseDateTimeError)` - If the input string cannot be parsed as a date/time
+///
+/// # Errors
+///
+/// This function will return `Err(ParseDateTimeError::InvalidInput)` if the input string
+/// cannot be parsed as a date/time.
+pub fn parse_datetime_split<S: AsRef<str> + Clone>(
+    s: S,
+) -> Result<(NaiveDateTime, FixedOffset), ParseDateTimeError> {
+    let dt = parse_datetime(s)?;
+    Ok((dt.naive_local(), *dt.offset()))
+}
 
-        #[test]
-        fn test_space_sep() {
-            env::set_var("TZ", "UTC");
-            let dt = "2021-02-15 06:37:47";
-            let actual = parse_datetime(dt);
-            assert_eq!(actual.unwrap().timestamp(), TEST_TIME);
-        }
+/// Parses a time string and returns a `DateTime` representing the
+/// absolute time of the string. Unlike [`parse_datetime`], a date without a
+/// time is taken to mean the end of that day, i.e. 23:59:59.999999999, rather
+/// than midnight.
+///
+/// This is useful for inclusive upper bounds, e.g. an `--until` option.
+///
+/// # Arguments
+///
+/// * `s` - A string slice representing the time.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Local, TimeZone, Timelike};
+/// use parse_datetime::parse_datetime_end_of_day;
+///
+/// let until = parse_datetime_end_of_day("2022-11-14").unwrap();
+/// assert_eq!(until.format("%F %T").to_string(), "2022-11-14 23:59:59");
+/// assert_eq!(until.nanosecond(), 999_999_999);
+///
+/// let until = parse_datetime_end_of_day("2022-11-14 10:00").unwrap();
+/// assert_eq!(until, Local.with_ymd_and_hms(2022, 11, 14, 10, 0, 0).unwrap());
+/// ```
+///
+/// # Returns
+///
+/// * `Ok(DateTime<FixedOffset>)` - If the input string can be parsed as a time
+/// * `Err(ParseDateTimeError)` - If the input string cannot be parsed as a date/time
+///
+/// # Errors
+///
+/// This function will return `Err(ParseDateTimeError::InvalidInput)` if the input string
+/// cannot be parsed as a date/time.
+pub fn parse_datetime_end_of_day<S: AsRef<str> + Clone>(
+    s: S,
+) -> Result<DateTime<FixedOffset>, ParseDateTimeError> {
+    let date = Local::now();
+    if let Some(parsed) = parse_date_only(date, s.as_ref()) {
+        let end_of_day = NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999).unwrap();
+        return naive_dt_to_fixed_offset(date, parsed.and_time(end_of_day))
+            .map_err(|_| ParseDateTimeError::InvalidInput);
+    }
+    parse_datetime_at_date(date, s)
+}
 
-        #[test]
-        fn test_space_sep_offset() {
-            env::set_var("TZ", "UTC");
-            let dt = "2021-02-14 22:37:47 -0800";
+/// Parses a time string relative to a reference instant, which is taken in
+/// the local time zone. This is useful for tools such as `touch -r` that
+/// adjust the timestamp of a reference file.
+///
+/// # Arguments
+///
+/// * `reference` - The instant that relative times are resolved against.
+/// * `s` - A string slice representing the time.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use parse_datetime::parse_datetime_relative_to;
+///
+/// let reference = Utc.with_ymd_and_hms(2022, 11, 14, 10, 0, 0).unwrap();
+/// let time = parse_datetime_relative_to(reference, "+1 hour").unwrap();
+/// assert_eq!(time, reference + Duration::hours(1));
+/// ```
+///
+/// # Returns
+///
+/// * `Ok(DateTime<FixedOffset>)` - If the input string can be parsed as a time
+/// * `Err(ParseDateTimeError)` - If the input string cannot be parsed as a date/time
+///
+/// # Errors
+///
+/// This function will return `Err(ParseDateTimeError::InvalidInput)` if the input string
+/// cannot be parsed as a date/time.
+pub fn parse_datetime_relative_to<S: AsRef<str> + Clone>(
+    reference: DateTime<Utc>,
+    s: S,
+) -> Result<DateTime<FixedOffset>, ParseDateTimeError> {
+    parse_datetime_at_date(reference.with_timezone(&Local), s)
+}
+
+/// Parses a unix timestamp such as "@1234" or "@-1.5" and returns the instant
+/// it represents in UTC, without resolving any time zone.
+///
+/// Only a timestamp on its own is accepted, so "@0 +1 day" is rejected.
+///
+/// # Arguments
+///
+/// * `s` - A string slice representing the timestamp.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use parse_datetime::parse_timestamp;
+///
+/// let time = parse_timestamp("@1668420000").unwrap();
+/// assert_eq!(time, Utc.with_ymd_and_hms(2022, 11, 14, 10, 0, 0).unwrap());
+/// ```
+///
+/// # Returns
+///
+/// * `Ok(DateTime<Utc>)` - If the input string can be parsed as a timestamp
+/// * `Err(ParseDateTimeError)` - If the input string cannot be parsed as a timestamp
+///
+/// # Errors
+///
+/// This function will return `Err(ParseDateTimeError::InvalidInput)` if the input string
+/// cannot be parsed as a timestamp, or if it is out of range.
+pub fn parse_timestamp<S: AsRef<str>>(s: S) -> Result<DateTime<Utc>, ParseDateTimeError> {
+    let (timestamp, nanos) = parse_timestamp::parse_timestamp(s.as_ref())
+        .map_err(|_| ParseDateTimeError::InvalidInput)?;
+    DateTime::from_timestamp(timestamp, nanos).ok_or(ParseDateTimeError::InvalidInput)
+}
+
+/// Parses a date and time string without an offset and returns the
+/// wall-clock `NaiveDateTime` as written, without resolving it in the local
+/// time zone.
+///
+/// This avoids the local time zone rejecting a wall-clock time that does not
+/// exist there, e.g. one skipped by a DST transition. Only absolute dates and
+/// times are accepted. Input with an offset or a relative time is rejected.
+///
+/// # Arguments
+///
+/// * `s` - A string slice representing the date and time.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use parse_datetime::parse_datetime_civil;
+///
+/// assert_eq!(
+///     parse_datetime_civil("2024-03-10 02:30").unwrap(),
+///     NaiveDate::from_ymd_opt(2024, 3, 10)
+///         .unwrap()
+///         .and_hms_opt(2, 30, 0)
+///         .unwrap()
+/// );
+/// ```
+///
+/// # Returns
+///
+/// * `Ok(NaiveDateTime)` - If the input string can be parsed as a date and time
+/// * `Err(ParseDateTimeError)` - If the input string cannot be parsed as a date and time
+///
+/// # Errors
+///
+/// This function will return `Err(ParseDateTimeError::InvalidInput)` if the input string
+/// cannot be parsed as a date and time without an offset.
+pub fn parse_datetime_civil<S: AsRef<str>>(s: S) -> Result<NaiveDateTime, ParseDateTimeError> {
+    if let Some(parsed) = parse_naive_date_time(s.as_ref()) {
+        return Ok(roll_over_leap_second(parsed.and_utc().fixed_offset()).naive_local());
+    }
+    match parse_date_only(Local::now(), s.as_ref()) {
+        Some(parsed) => Ok(parsed.and_time(NaiveTime::MIN)),
+        None => Err(ParseDateTimeError::InvalidInput),
+    }
+}
+
+// Parse formats with a date and a time but no offset
+fn parse_naive_date_time(s: &str) -> Option<NaiveDateTime> {
+    [
+        format::YYYYMMDDHHMMS_T_SEP,
+        format::YYYYMMDDHHMM,
+        format::YYYYMMDDHHMMS,
+        format::YYYYMMDDHHMMSS,
+        format::YYYY_MM_DD_HH_MM,
+        format::YYYYMMDDHHMM_DOT_SS,
+        format::POSIX_LOCALE,
+    ]
+    .into_iter()
+    .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+}
+
+// Parse formats that only specify a date
+fn parse_date_only(date: DateTime<Local>, s: &str) -> Option<NaiveDate> {
+    let ts = s.to_owned() + " 0000";
+    for fmt in [format::ISO_8601, format::ISO_8601_NO_SEP] {
+        let f = fmt.to_owned() + " %H%M";
+        if let Ok(parsed) = NaiveDateTime::parse_from_str(&ts, &f) {
+            return Some(parsed.date());
+        }
+    }
+
+    // Parse years beyond 9999, which chrono only accepts with an explicit
+    // sign. Years past chrono's range, such as 2147485547, are still rejected
+    if Regex::new(r"^\d{5,}-").ok()?.is_match(s) {
+        if let Ok(parsed) = NaiveDate::parse_from_str(&format!("+{s}"), format::ISO_8601) {
+            return Some(parsed);
+        }
+    }
+
+    // Parse ISO 8601 ordinal dates, i.e. a year and a day of the year. The
+    // day always has three digits, so "2023-06" isn't taken as January 6
+    if Regex::new(r"^\d{4}-\d{3}$").ok()?.is_match(s) {
+        if let Ok(parsed) = NaiveDate::parse_from_str(s, format::ISO_8601_ORDINAL) {
+            return Some(parsed);
+        }
+    }
+
+    // Parse a day of the current month, e.g. "the 3rd"
+    if let Some(day) = parse_day_of_month::parse_day_of_month(s) {
+        return date.date_naive().with_day(day);
+    }
+
+    // Parse ISO 8601 week dates - assume monday if the weekday is omitted
+    for ts in [s.to_owned(), s.to_owned() + "-1"] {
+        if let Ok(parsed) = NaiveDate::parse_from_str(&ts, format::ISO_8601_WEEK) {
+            return Some(parsed);
+        }
+    }
+
+    // Parse ISO 8601 dates with an era, e.g. "0044-03-15 BC". Years are
+    // numbered astronomically, as with a leading minus, so 1 BC is year 0
+    // and 44 BC is year -43
+    let era = Regex::new(r"(?i)^\s*(?<year>\d+)-(?<month_day>\d{1,2}-\d{1,2})\s+(?<era>bc|ad)\s*$");
+    if let Some(captures) = era.ok()?.captures(s) {
+        let year = captures["year"]
+            .parse::<i32>()
+            .ok()
+            .filter(|&year| year > 0)?;
+        let year = if captures["era"].eq_ignore_ascii_case("bc") {
+            1 - year
+        } else {
+            year
+        };
+        let ts = format!("{year}-{}", &captures["month_day"]);
+        return NaiveDate::parse_from_str(&ts, format::ISO_8601).ok();
+    }
+
+    None
+}
+
+/// Parses a weekday with an optional "next", "last", "this" or ordinal word
+/// and an optional time of day, e.g. "next monday at 14:00", relative to
+/// `date`.
+///
+/// A plain weekday is the first such day from today on, whereas "next" and
+/// "last" skip today, as with gnu date. An ordinal such as "third" counts
+/// weeks on from the next such day.
+///
+/// With a `week_start`, "this" is the day in the current week instead, and
+/// "next", "last" and ordinals count whole weeks on from it.
+fn parse_weekday_at_date(
+    date: DateTime<Local>,
+    s: &str,
+    week_start: Option<WeekStart>,
+) -> Option<DateTime<FixedOffset>> {
+    static WEEKDAY: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^\s*(?<weekday>.+?)(?:\s+(?:at\s+)?(?<time>\d.*?))?\s*$").unwrap()
+    });
+    let captures = WEEKDAY.captures(s)?;
+    let (ordinal, weekday) = parse_weekday::parse_relative_weekday(&captures["weekday"])?;
+
+    // A count such as "2 fridays ago" may be large, so the weeks are checked
+    let (days, weeks) = match (week_start, ordinal) {
+        (Some(week_start), Some(ordinal)) => {
+            let first_day = match week_start {
+                WeekStart::Monday => Weekday::Mon,
+                WeekStart::Sunday => Weekday::Sun,
+            };
+            let days = i64::from(weekday.days_since(first_day))
+                - i64::from(date.weekday().days_since(first_day));
+            (days, ordinal)
+        }
+        (_, ordinal) => {
+            let ordinal = ordinal.unwrap_or(0);
+            let days_ahead = i64::from(weekday.days_since(date.weekday()));
+            (
+                days_ahead,
+                ordinal - i64::from(ordinal > 0 && days_ahead != 0),
+            )
+        }
+    };
+    let days = weeks.checked_mul(7)?.checked_add(days)?;
+    let day = beginning_of_day(date).checked_add_signed(Duration::try_days(days)?)?;
+
+    match captures.name("time") {
+        Some(time) => parse_time_only_str::parse_time_only(day, time.as_str()),
+        None => Some(DateTime::<FixedOffset>::from(day)),
+    }
+}
+
+fn beginning_of_day(date: DateTime<Local>) -> DateTime<Local> {
+    date.with_hour(0)
+        .unwrap()
+        .with_minute(0)
+        .unwrap()
+        .with_second(0)
+        .unwrap()
+        .with_nanosecond(0)
+        .unwrap()
+}
+
+// A second value of 60 is a leap second, which chrono represents as a
+// nanosecond value of one second or more. gnu date rolls it over into the
+// following minute instead, e.g. "23:59:60" becomes "00:00:00" of the next day.
+fn roll_over_leap_second(dt: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+    match dt.nanosecond().checked_sub(1_000_000_000) {
+        Some(nanos) => DateTime::from_timestamp(dt.timestamp() + 1, nanos)
+            .map_or(dt, |rolled| rolled.with_timezone(dt.offset())),
+        None => dt,
+    }
+}
+
+// Convert NaiveDateTime to DateTime<FixedOffset> by assuming the offset
+// is local time
+fn naive_dt_to_fixed_offset(
+    local: DateTime<Local>,
+    dt: NaiveDateTime,
+) -> Result<DateTime<FixedOffset>, ()> {
+    match local.offset().from_local_datetime(&dt) {
+        LocalResult::Single(dt) => Ok(dt),
+        _ => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    static TEST_TIME: i64 = 1613371067;
+
+    #[cfg(test)]
+    mod iso_8601 {
+        use std::env;
+
+        use crate::ParseDateTimeError;
+        use crate::{parse_datetime, tests::TEST_TIME};
+
+        #[test]
+        fn test_t_sep() {
+            env::set_var("TZ", "UTC");
+            let dt = "2021-02-15T06:37:47";
+            let actual = parse_datetime(dt);
+            assert_eq!(actual.unwrap().timestamp(), TEST_TIME);
+        }
+
+        #[test]
+        fn test_space_sep() {
+            env::set_var("TZ", "UTC");
+            let dt = "2021-02-15 06:37:47";
+            let actual = parse_datetime(dt);
+            assert_eq!(actual.unwrap().timestamp(), TEST_TIME);
+        }
+
+        #[test]
+        fn test_space_sep_offset() {
+            env::set_var("TZ", "UTC");
+            let dt = "2021-02-14 22:37:47 -0800";
             let actual = parse_datetime(dt);
             assert_eq!(actual.unwrap().timestamp(), TEST_TIME);
         }
@@ -326,7 +1084,7 @@ mod tests {
     #[cfg(test)]
     mod formats {
         use crate::parse_datetime;
-        use chrono::{DateTime, Local, TimeZone};
+        use chrono::{DateTime, Local, NaiveDate, TimeZone};
 
         #[test]
         fn single_digit_month_day() {
@@ -338,100 +1096,559 @@ mod tests {
             assert_eq!(Ok(expected), parse_datetime("1987-05-7"));
             assert_eq!(Ok(expected), parse_datetime("1987-5-7"));
         }
+
+        #[test]
+        fn years_beyond_9999() {
+            for (s, (year, month, day)) in [
+                ("10000-12-31", (10000, 12, 31)),
+                ("99999-1-31", (99999, 1, 31)),
+            ] {
+                assert_eq!(
+                    parse_datetime(s).unwrap().date_naive(),
+                    NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+                    "{s}"
+                );
+            }
+
+            // beyond the range of chrono
+            for s in ["300000-01-01", "2147485547-12-31", "10000-13-01"] {
+                assert!(parse_datetime(s).is_err(), "{s}");
+            }
+        }
+
+        #[test]
+        fn eight_digit_number_is_a_date() {
+            // gnu date reads an 8-digit number as yyyymmdd
+            let x = Local.with_ymd_and_hms(1111, 11, 11, 0, 0, 0).unwrap();
+            assert_eq!(Ok(DateTime::fixed_offset(&x)), parse_datetime("11111111"));
+        }
     }
 
     #[cfg(test)]
-    mod offsets {
-        use chrono::Local;
+    mod leap_second {
+        use crate::{parse_datetime, parse_datetime_at_date};
+        use chrono::{Local, TimeZone, Timelike, Utc};
+        use std::env;
+
+        #[test]
+        fn test_leap_second_with_date() {
+            let expected = Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap();
+            assert_eq!(parse_datetime("2016-12-31 23:59:60Z"), Ok(expected.into()));
+            assert_eq!(parse_datetime("2016-12-31T23:59:60Z"), Ok(expected.into()));
+        }
+
+        #[test]
+        fn test_leap_second_without_date() {
+            env::set_var("TZ", "UTC");
+            let now = Local.with_ymd_and_hms(2016, 12, 31, 12, 0, 0).unwrap();
+            let parsed = parse_datetime_at_date(now, "23:59:60").unwrap();
+            assert_eq!(parsed, Local.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap());
+            assert_eq!(parsed.nanosecond(), 0);
+        }
+
+        #[test]
+        fn test_second_above_sixty() {
+            assert!(parse_datetime("2016-12-31 23:59:61Z").is_err());
+        }
+    }
 
+    #[cfg(test)]
+    mod rfc2822 {
         use crate::parse_datetime;
-        use crate::ParseDateTimeError;
+        use chrono::{TimeZone, Utc};
 
         #[test]
-        fn test_positive_offsets() {
-            let offsets = vec![
-                "UTC+07:00",
-                "UTC+0700",
-                "UTC+07",
-                "Z+07:00",
-                "Z+0700",
-                "Z+07",
-            ];
+        fn test_email_date_header() {
+            let expected = Utc.with_ymd_and_hms(2022, 2, 1, 6, 37, 47).unwrap();
+            for s in [
+                "Tue, 1 Feb 2022 06:37:47 +0000",
+                "Tue, 1 Feb 2022 06:37:47 GMT",
+                "Tue, 1 Feb 2022 01:37:47 EST",
+                "Tue, 1 Feb 2022 09:37:47 C",
+            ] {
+                assert_eq!(parse_datetime(s).unwrap(), expected, "{s}");
+            }
+        }
 
-            let expected = format!("{}{}", Local::now().format("%Y%m%d"), "0000+0700");
-            for offset in offsets {
-                let actual = parse_datetime(offset).unwrap();
-                assert_eq!(expected, format!("{}", actual.format("%Y%m%d%H%M%z")));
+        #[test]
+        fn test_mismatched_weekday() {
+            assert!(parse_datetime("Wed, 1 Feb 2022 06:37:47 +0000").is_err());
+        }
+    }
+
+    #[cfg(test)]
+    mod abbreviations {
+        use crate::{parse_datetime, parse_datetime_at_date, ParseDateTimeError};
+        use chrono::{FixedOffset, Local, TimeZone, Utc};
+
+        #[test]
+        fn test_date_time_with_abbreviation() {
+            let expected = Utc.with_ymd_and_hms(2022, 11, 14, 18, 0, 0).unwrap();
+            for s in [
+                "2022-11-14 10:00 PST",
+                "2022-11-14 11:00 PDT",
+                "2022-11-14 19:00 CET",
+                "2022-11-14 18:00 UTC",
+                "2022-11-14 23:30 IST",
+                "Tue, 15 Nov 2022 03:00:00 JST",
+            ] {
+                assert_eq!(parse_datetime(s).unwrap(), expected, "{s}");
             }
         }
 
         #[test]
-        fn test_partial_offset() {
-            let offsets = vec!["UTC+00:15", "UTC+0015", "Z+00:15", "Z+0015"];
-            let expected = format!("{}{}", Local::now().format("%Y%m%d"), "0000+0015");
-            for offset in offsets {
-                let actual = parse_datetime(offset).unwrap();
-                assert_eq!(expected, format!("{}", actual.format("%Y%m%d%H%M%z")));
+        fn test_time_with_abbreviation() {
+            let date = Local.with_ymd_and_hms(2024, 3, 3, 0, 0, 0).unwrap();
+            let parsed = parse_datetime_at_date(date, "21:04 CEST").unwrap();
+            assert_eq!(parsed.offset(), &FixedOffset::east_opt(7200).unwrap());
+            assert_eq!(parsed.format("%H:%M").to_string(), "21:04");
+        }
+
+        #[test]
+        fn test_twelve_hour_time_with_abbreviation() {
+            let date = Local.with_ymd_and_hms(2024, 3, 3, 0, 0, 0).unwrap();
+            for (s, expected) in [
+                ("8:30 PM EST", "20:30 -0500"),
+                ("8:30 am PST", "08:30 -0800"),
+                ("12:15 AM CEST", "00:15 +0200"),
+                ("8:30:00 PM EST", "20:30 -0500"),
+            ] {
+                let parsed = parse_datetime_at_date(date, s).unwrap();
+                assert_eq!(parsed.format("%H:%M %z").to_string(), expected, "{s}");
             }
         }
 
         #[test]
-        fn invalid_offset_format() {
-            let invalid_offsets = vec!["+0700", "UTC+2", "Z-1", "UTC+01005"];
-            for offset in invalid_offsets {
+        fn test_unknown_abbreviation() {
+            assert_eq!(
+                parse_datetime("2022-11-14 10:00 AEST"),
+                Err(ParseDateTimeError::InvalidInput)
+            );
+        }
+
+        #[test]
+        fn test_lowercase_word_is_not_an_abbreviation() {
+            // "cat" would otherwise be Central Africa Time
+            for s in ["10:00 cat", "10:00 Cat", "2022-11-14 11:00 pdt"] {
                 assert_eq!(
-                    parse_datetime(offset),
-                    Err(ParseDateTimeError::InvalidInput)
+                    parse_datetime(s),
+                    Err(ParseDateTimeError::InvalidInput),
+                    "{s}"
                 );
             }
         }
     }
 
     #[cfg(test)]
-    mod relative_time {
+    mod iso_basic {
         use crate::parse_datetime;
+        use chrono::{FixedOffset, TimeZone};
+
         #[test]
-        fn test_positive_offsets() {
-            let relative_times = vec![
-                "today",
-                "yesterday",
-                "1 minute",
-                "3 hours",
-                "1 year 3 months",
-            ];
+        fn test_basic_format_fractional_seconds() {
+            let utc = FixedOffset::east_opt(0).unwrap();
 
-            for relative_time in relative_times {
-                assert!(parse_datetime(relative_time).is_ok());
-            }
+            let expected = utc
+                .with_ymd_and_hms(2021, 2, 15, 6, 37, 47)
+                .unwrap()
+                .checked_add_signed(chrono::Duration::nanoseconds(123_456_789))
+                .unwrap();
+            assert_eq!(parse_datetime("20210215T063747.123456789Z"), Ok(expected));
+
+            let expected = utc
+                .with_ymd_and_hms(2021, 2, 15, 6, 37, 47)
+                .unwrap()
+                .checked_add_signed(chrono::Duration::milliseconds(500))
+                .unwrap();
+            assert_eq!(parse_datetime("20210215T063747,5Z"), Ok(expected));
+            assert_eq!(parse_datetime("20210215T063747.5Z"), Ok(expected));
+        }
+
+        #[test]
+        fn test_basic_format_with_offset() {
+            let expected = FixedOffset::east_opt(3600)
+                .unwrap()
+                .with_ymd_and_hms(2021, 2, 15, 6, 37, 47)
+                .unwrap();
+            assert_eq!(parse_datetime("20210215T063747+0100"), Ok(expected));
+            assert_eq!(parse_datetime("20210215T063747+01:00"), Ok(expected));
         }
     }
 
     #[cfg(test)]
-    mod weekday {
+    mod iso_week {
+        use crate::parse_datetime;
         use chrono::{DateTime, Local, TimeZone};
 
-        use crate::parse_datetime_at_date;
-
-        fn get_formatted_date(date: DateTime<Local>, weekday: &str) -> String {
-            let result = parse_datetime_at_date(date, weekday).unwrap();
-
-            result.format("%F %T %f").to_string()
+        fn local_midnight(year: i32, month: u32, day: u32) -> DateTime<Local> {
+            Local.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
         }
 
         #[test]
-        fn test_weekday() {
-            // add some constant hours and minutes and seconds to check its reset
-            let date = Local.with_ymd_and_hms(2023, 2, 28, 10, 12, 3).unwrap();
-
-            // 2023-2-28 is tuesday
+        fn test_week_defaults_to_monday() {
             assert_eq!(
-                get_formatted_date(date, "tuesday"),
-                "2023-02-28 00:00:00 000000000"
+                parse_datetime("2024-W05").unwrap(),
+                local_midnight(2024, 1, 29)
             );
-
-            // 2023-3-01 is wednesday
             assert_eq!(
-                get_formatted_date(date, "wed"),
+                parse_datetime("2024-W01").unwrap(),
+                local_midnight(2024, 1, 1)
+            );
+        }
+
+        #[test]
+        fn test_week_across_year_boundary() {
+            // ISO week 1 of 2025 starts in december 2024
+            assert_eq!(
+                parse_datetime("2025-W01").unwrap(),
+                local_midnight(2024, 12, 30)
+            );
+            assert_eq!(
+                parse_datetime("2020-W53-5").unwrap(),
+                local_midnight(2021, 1, 1)
+            );
+        }
+
+        #[test]
+        fn test_week_with_weekday() {
+            assert_eq!(
+                parse_datetime("2024-W05-3").unwrap(),
+                local_midnight(2024, 1, 31)
+            );
+            assert_eq!(
+                parse_datetime("2024-W05-7").unwrap(),
+                local_midnight(2024, 2, 4)
+            );
+        }
+
+        #[test]
+        fn test_week_year_differs_from_calendar_year() {
+            // 2021-01-01 belongs to the last ISO week of 2020, so "%G" and
+            // "%Y" disagree on its year. A calendar date is always read with
+            // the calendar year and only the "W" form uses the week-year.
+            let calendar = parse_datetime("2021-01-01").unwrap();
+            let week = parse_datetime("2020-W53-5").unwrap();
+            assert_eq!(calendar, week);
+            assert_eq!(calendar.format("%Y").to_string(), "2021");
+            assert_eq!(calendar.format("%G").to_string(), "2020");
+            assert_eq!(
+                parse_datetime(calendar.format("%G-W%V-%u").to_string()).unwrap(),
+                calendar
+            );
+        }
+
+        #[test]
+        fn test_known_week_dates() {
+            for (s, (year, month, day)) in [
+                // week 01 can start in the previous gregorian year or after
+                // the first days of january
+                ("2021-W01-1", (2021, 1, 4)),
+                ("2008-W01-1", (2007, 12, 31)),
+                ("2022-W45-2", (2022, 11, 8)),
+                ("2022-W45", (2022, 11, 7)),
+                // long years have a week 53
+                ("2015-W53-7", (2016, 1, 3)),
+                ("2009-W53-7", (2010, 1, 3)),
+                ("2026-W53-4", (2026, 12, 31)),
+            ] {
+                assert_eq!(
+                    parse_datetime(s).unwrap(),
+                    local_midnight(year, month, day),
+                    "{s}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_invalid_week() {
+            for s in [
+                "2024-W54",
+                "2024-W00",
+                "2023-W53",
+                "2021-W53",
+                "2024-W05-8",
+                "2024-W05-0",
+            ] {
+                assert!(parse_datetime(s).is_err(), "{s}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod iso_ordinal {
+        use crate::parse_datetime;
+        use chrono::{Local, TimeZone};
+
+        #[test]
+        fn test_ordinal_dates() {
+            for (s, (year, month, day)) in [
+                ("2023-001", (2023, 1, 1)),
+                ("2023-060", (2023, 3, 1)),
+                ("2024-060", (2024, 2, 29)),
+                ("2023-365", (2023, 12, 31)),
+                ("2024-366", (2024, 12, 31)),
+                ("2023-032", (2023, 2, 1)),
+            ] {
+                assert_eq!(
+                    parse_datetime(s).unwrap(),
+                    Local.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap(),
+                    "{s}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_ordinal_does_not_shadow_calendar_dates() {
+            assert_eq!(
+                parse_datetime("2023-06-01").unwrap(),
+                Local.with_ymd_and_hms(2023, 6, 1, 0, 0, 0).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_invalid_ordinal_dates() {
+            for s in [
+                "2023-366",
+                "2024-367",
+                "2023-000",
+                "2023-0060",
+                "2023-06",
+                "2023-6",
+                "2023-32",
+            ] {
+                assert!(parse_datetime(s).is_err(), "{s}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod era {
+        use crate::parse_datetime;
+        use chrono::{Datelike, NaiveDate, Weekday};
+
+        fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+            NaiveDate::from_ymd_opt(year, month, day).unwrap()
+        }
+
+        #[test]
+        fn test_negative_years() {
+            // years are astronomical, so -44 is 45 BC
+            for (s, date) in [
+                ("-44-03-15", ymd(-44, 3, 15)),
+                ("-0044-03-15", ymd(-44, 3, 15)),
+                ("0000-12-31", ymd(0, 12, 31)),
+            ] {
+                assert_eq!(parse_datetime(s).unwrap().date_naive(), date, "{s}");
+            }
+        }
+
+        #[test]
+        fn test_bc_and_ad() {
+            for (s, date) in [
+                // the ides of march, 44 BC
+                ("0044-03-15 BC", ymd(-43, 3, 15)),
+                ("0001-12-31 BC", ymd(0, 12, 31)),
+                ("0001-01-01 ad", ymd(1, 1, 1)),
+                ("1066-10-14 AD", ymd(1066, 10, 14)),
+                // 45 BC is a leap year in the proleptic gregorian calendar
+                ("0045-02-29 BC", ymd(-44, 2, 29)),
+            ] {
+                assert_eq!(parse_datetime(s).unwrap().date_naive(), date, "{s}");
+            }
+            assert_eq!(
+                parse_datetime("0001-01-01 AD").unwrap().weekday(),
+                Weekday::Mon
+            );
+        }
+
+        #[test]
+        fn test_invalid_eras() {
+            for s in [
+                "0000-01-01 BC",
+                "0044-02-29 BC",
+                "-0044-03-15 BC",
+                "0044-03-15 BCE",
+                "44 BC",
+            ] {
+                assert!(parse_datetime(s).is_err(), "{s}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod day_of_month {
+        use crate::{parse_datetime_at_date, ParseDateTimeError};
+        use chrono::{Local, TimeZone};
+
+        #[test]
+        fn test_day_of_month() {
+            let date = Local.with_ymd_and_hms(2025, 1, 20, 10, 12, 3).unwrap();
+            assert_eq!(
+                parse_datetime_at_date(date, "the 3rd").unwrap(),
+                Local.with_ymd_and_hms(2025, 1, 3, 0, 0, 0).unwrap()
+            );
+            assert_eq!(
+                parse_datetime_at_date(date, "31st").unwrap(),
+                Local.with_ymd_and_hms(2025, 1, 31, 0, 0, 0).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_day_out_of_month() {
+            let date = Local.with_ymd_and_hms(2025, 2, 20, 10, 12, 3).unwrap();
+            assert_eq!(
+                parse_datetime_at_date(date, "the 29th"),
+                Err(ParseDateTimeError::InvalidInput)
+            );
+
+            let date = Local.with_ymd_and_hms(2024, 2, 20, 10, 12, 3).unwrap();
+            assert_eq!(
+                parse_datetime_at_date(date, "the 29th").unwrap(),
+                Local.with_ymd_and_hms(2024, 2, 29, 0, 0, 0).unwrap()
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod offsets {
+        use chrono::Local;
+
+        use crate::parse_datetime;
+        use crate::ParseDateTimeError;
+
+        #[test]
+        fn test_positive_offsets() {
+            let offsets = vec![
+                "UTC+07:00",
+                "UTC+0700",
+                "UTC+07",
+                "Z+07:00",
+                "Z+0700",
+                "Z+07",
+            ];
+
+            let expected = format!("{}{}", Local::now().format("%Y%m%d"), "0000+0700");
+            for offset in offsets {
+                let actual = parse_datetime(offset).unwrap();
+                assert_eq!(expected, format!("{}", actual.format("%Y%m%d%H%M%z")));
+            }
+        }
+
+        #[test]
+        fn test_partial_offset() {
+            let offsets = vec!["UTC+00:15", "UTC+0015", "Z+00:15", "Z+0015"];
+            let expected = format!("{}{}", Local::now().format("%Y%m%d"), "0000+0015");
+            for offset in offsets {
+                let actual = parse_datetime(offset).unwrap();
+                assert_eq!(expected, format!("{}", actual.format("%Y%m%d%H%M%z")));
+            }
+        }
+
+        #[test]
+        fn test_offset_with_seconds() {
+            // gnu date only accepts hours and minutes in a numeric offset
+            for s in ["2022-11-14 10:00 +05:30:15", "202211141000 +053015"] {
+                assert_eq!(
+                    parse_datetime(s),
+                    Err(ParseDateTimeError::InvalidInput),
+                    "{s}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_minutes_only_offset() {
+            let date = Local::now().format("%Y%m%d");
+            for (offset, expected) in [
+                ("UTC+:30", "0000+0030"),
+                ("UTC-:15", "0000-0015"),
+                ("Z+:30", "0000+0030"),
+                ("Z-:45", "0000-0045"),
+            ] {
+                let actual = parse_datetime(offset).unwrap();
+                assert_eq!(
+                    format!("{date}{expected}"),
+                    format!("{}", actual.format("%Y%m%d%H%M%z"))
+                );
+            }
+        }
+
+        #[test]
+        fn invalid_offset_format() {
+            let invalid_offsets = vec!["+0700", "UTC+2", "Z-1", "UTC+01005", "UTC+5:", "UTC+:5"];
+            for offset in invalid_offsets {
+                assert_eq!(
+                    parse_datetime(offset),
+                    Err(ParseDateTimeError::InvalidInput)
+                );
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod relative_time {
+        use crate::parse_datetime;
+        #[test]
+        fn test_positive_offsets() {
+            let relative_times = vec![
+                "today",
+                "yesterday",
+                "1 minute",
+                "3 hours",
+                "1 year 3 months",
+            ];
+
+            for relative_time in relative_times {
+                assert!(parse_datetime(relative_time).is_ok());
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod qualifiers {
+        use crate::parse_datetime;
+
+        #[test]
+        fn test_qualifiers_only_apply_to_relative_times() {
+            assert!(parse_datetime("about 3 hours").is_ok());
+
+            for s in [
+                "about 10:00",
+                "about monday",
+                "exactly 2022-11-14",
+                "about @0",
+            ] {
+                assert!(parse_datetime(s).is_err(), "{s}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod weekday {
+        use chrono::{DateTime, Local, TimeZone};
+
+        use crate::{parse_datetime_at_date, parse_datetime_at_date_with_week_start, WeekStart};
+
+        fn get_formatted_date(date: DateTime<Local>, weekday: &str) -> String {
+            let result = parse_datetime_at_date(date, weekday).unwrap();
+
+            result.format("%F %T %f").to_string()
+        }
+
+        #[test]
+        fn test_weekday() {
+            // add some constant hours and minutes and seconds to check its reset
+            let date = Local.with_ymd_and_hms(2023, 2, 28, 10, 12, 3).unwrap();
+
+            // 2023-2-28 is tuesday
+            assert_eq!(
+                get_formatted_date(date, "tuesday"),
+                "2023-02-28 00:00:00 000000000"
+            );
+
+            // 2023-3-01 is wednesday
+            assert_eq!(
+                get_formatted_date(date, "wed"),
                 "2023-03-01 00:00:00 000000000"
             );
 
@@ -446,21 +1663,694 @@ mod tests {
             );
 
             assert_eq!(
-                get_formatted_date(date, "sat"),
-                "2023-03-04 00:00:00 000000000"
+                get_formatted_date(date, "sat"),
+                "2023-03-04 00:00:00 000000000"
+            );
+
+            assert_eq!(
+                get_formatted_date(date, "sun"),
+                "2023-03-05 00:00:00 000000000"
+            );
+        }
+
+        #[test]
+        fn test_ordinal_weekday() {
+            // 2023-2-28 is tuesday, and an ordinal counts from the next tuesday
+            let date = Local.with_ymd_and_hms(2023, 2, 28, 10, 12, 3).unwrap();
+
+            for (s, expected) in [
+                ("first wed", "2023-03-01 00:00:00 000000000"),
+                ("third wed", "2023-03-15 00:00:00 000000000"),
+                ("first tuesday", "2023-03-07 00:00:00 000000000"),
+                ("twelfth tuesday", "2023-05-23 00:00:00 000000000"),
+                ("thirteenth tuesday", "2023-05-30 00:00:00 000000000"),
+            ] {
+                assert_eq!(get_formatted_date(date, s), expected, "{s}");
+            }
+
+            // "second" is the unit, not an ordinal
+            assert_eq!(
+                get_formatted_date(date, "second tuesday"),
+                "2023-02-28 00:00:01 000000000"
+            );
+        }
+
+        #[test]
+        fn test_next_and_last_weekday() {
+            // 2023-2-28 is tuesday, and "next" and "last" skip today
+            let date = Local.with_ymd_and_hms(2023, 2, 28, 10, 12, 3).unwrap();
+
+            assert_eq!(
+                get_formatted_date(date, "next tue"),
+                "2023-03-07 00:00:00 000000000"
+            );
+            assert_eq!(
+                get_formatted_date(date, "this tue"),
+                "2023-02-28 00:00:00 000000000"
+            );
+            assert_eq!(
+                get_formatted_date(date, "next fri"),
+                "2023-03-03 00:00:00 000000000"
+            );
+            assert_eq!(
+                get_formatted_date(date, "last tue"),
+                "2023-02-21 00:00:00 000000000"
+            );
+            assert_eq!(
+                get_formatted_date(date, "last mon"),
+                "2023-02-27 00:00:00 000000000"
+            );
+        }
+
+        #[test]
+        fn test_weekday_ago() {
+            // 2023-2-28 is tuesday, and "ago" counts the weekdays backwards
+            let date = Local.with_ymd_and_hms(2023, 2, 28, 10, 12, 3).unwrap();
+
+            for (s, expected) in [
+                ("2 fridays", "2023-03-10 00:00:00 000000000"),
+                ("2 fridays ago", "2023-02-17 00:00:00 000000000"),
+                ("3 fridays ago", "2023-02-10 00:00:00 000000000"),
+                ("2 wed ago", "2023-02-15 00:00:00 000000000"),
+                ("2 tue ago", "2023-02-14 00:00:00 000000000"),
+                ("this friday ago", "2023-03-03 00:00:00 000000000"),
+                ("last monday ago", "2023-03-06 00:00:00 000000000"),
+                ("2 fridays ago 14:00", "2023-02-17 14:00:00 000000000"),
+            ] {
+                assert_eq!(get_formatted_date(date, s), expected, "{s}");
+            }
+
+            for s in [
+                "fridays ago ago",
+                "99999999999999 fridays ago",
+                "-2 fridays",
+            ] {
+                assert!(parse_datetime_at_date(date, s).is_err(), "{s}");
+            }
+        }
+
+        #[test]
+        fn test_week_start() {
+            // 2024-03-13 is wednesday
+            let date = Local.with_ymd_and_hms(2024, 3, 13, 10, 12, 3).unwrap();
+            let format = |s, week_start| {
+                parse_datetime_at_date_with_week_start(date, s, week_start)
+                    .unwrap()
+                    .format("%F %T")
+                    .to_string()
+            };
+
+            // "this sunday" ends a week that starts on monday, but starts one
+            // that starts on sunday
+            assert_eq!(
+                get_formatted_date(date, "this sunday"),
+                "2024-03-17 00:00:00 000000000"
+            );
+            assert_eq!(
+                format("this sunday", WeekStart::Monday),
+                "2024-03-17 00:00:00"
+            );
+            assert_eq!(
+                format("this sunday", WeekStart::Sunday),
+                "2024-03-10 00:00:00"
+            );
+
+            for (s, monday, sunday) in [
+                ("next sunday", "2024-03-24", "2024-03-17"),
+                ("last sunday", "2024-03-10", "2024-03-03"),
+                ("this monday", "2024-03-11", "2024-03-11"),
+                ("third friday", "2024-04-05", "2024-04-05"),
+                ("next friday 14:00", "2024-03-22", "2024-03-22"),
+            ] {
+                assert_eq!(&format(s, WeekStart::Monday)[..10], monday, "{s}");
+                assert_eq!(&format(s, WeekStart::Sunday)[..10], sunday, "{s}");
+            }
+
+            // a plain weekday still moves forward to its next occurrence
+            for week_start in [WeekStart::Monday, WeekStart::Sunday] {
+                assert_eq!(format("sunday", week_start), "2024-03-17 00:00:00");
+                assert_eq!(format("monday", week_start), "2024-03-18 00:00:00");
+            }
+        }
+
+        #[test]
+        fn test_weekday_with_time_and_zone() {
+            let date = Local.with_ymd_and_hms(2023, 2, 28, 10, 12, 3).unwrap();
+
+            for (s, expected) in [
+                ("monday 10:00 EST", "2023-03-06T10:00:00-05:00"),
+                ("monday 10:00 PST", "2023-03-06T10:00:00-08:00"),
+                ("next monday 14:00 CET", "2023-03-06T14:00:00+01:00"),
+                ("monday at 9:00 PM EST", "2023-03-06T21:00:00-05:00"),
+            ] {
+                assert_eq!(
+                    parse_datetime_at_date(date, s).unwrap().to_rfc3339(),
+                    expected,
+                    "{s}"
+                );
+            }
+
+            let parsed = parse_datetime_at_date(date, "monday at 9:00 PM").unwrap();
+            assert_eq!(parsed.format("%F %T").to_string(), "2023-03-06 21:00:00");
+            assert_eq!(parsed.offset(), date.offset());
+        }
+
+        #[test]
+        fn test_weekday_with_time_and_relative() {
+            let date = Local.with_ymd_and_hms(2023, 2, 28, 10, 12, 3).unwrap();
+
+            assert_eq!(
+                get_formatted_date(date, "monday 14:00"),
+                "2023-03-06 14:00:00 000000000"
+            );
+            assert_eq!(
+                get_formatted_date(date, "monday at 14:00"),
+                "2023-03-06 14:00:00 000000000"
+            );
+
+            // the order of the weekday and the relative time doesn't matter
+            for s in [
+                "thursday 14:00 + 2 days",
+                "thursday 14:00 +2 days",
+                "+2 days thursday 14:00",
+                "2 days after thursday 14:00",
+            ] {
+                assert_eq!(
+                    get_formatted_date(date, s),
+                    "2023-03-04 14:00:00 000000000",
+                    "{s}"
+                );
+            }
+            assert_eq!(
+                get_formatted_date(date, "next monday + 2 days"),
+                "2023-03-08 00:00:00 000000000"
+            );
+            assert_eq!(
+                get_formatted_date(date, "monday 1 week ago"),
+                "2023-02-27 00:00:00 000000000"
+            );
+
+            for s in ["monday foo", "monday 14:00 foo", "monday 25:00 +2 days"] {
+                assert!(parse_datetime_at_date(date, s).is_err(), "{s}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod business_day {
+        use chrono::{DateTime, Local, TimeZone};
+
+        use crate::parse_datetime_at_date;
+
+        fn get_formatted_date(date: DateTime<Local>, s: &str) -> String {
+            let result = parse_datetime_at_date(date, s).unwrap();
+
+            result.format("%F %T %f").to_string()
+        }
+
+        #[test]
+        fn test_next_business_day() {
+            // 2024-03-15 is friday
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            assert_eq!(
+                get_formatted_date(date, "next business day"),
+                "2024-03-18 00:00:00 000000000"
+            );
+
+            // 2024-03-16 is saturday
+            let date = Local.with_ymd_and_hms(2024, 3, 16, 10, 12, 3).unwrap();
+            assert_eq!(
+                get_formatted_date(date, "next business day"),
+                "2024-03-18 00:00:00 000000000"
+            );
+
+            // 2024-03-19 is tuesday
+            let date = Local.with_ymd_and_hms(2024, 3, 19, 10, 12, 3).unwrap();
+            assert_eq!(
+                get_formatted_date(date, "next business day"),
+                "2024-03-20 00:00:00 000000000"
+            );
+        }
+
+        #[test]
+        fn test_previous_business_day() {
+            // 2024-03-18 is monday
+            let date = Local.with_ymd_and_hms(2024, 3, 18, 10, 12, 3).unwrap();
+            assert_eq!(
+                get_formatted_date(date, "previous business day"),
+                "2024-03-15 00:00:00 000000000"
+            );
+            assert_eq!(
+                get_formatted_date(date, "last business day"),
+                "2024-03-15 00:00:00 000000000"
+            );
+
+            // 2024-03-20 is wednesday
+            let date = Local.with_ymd_and_hms(2024, 3, 20, 10, 12, 3).unwrap();
+            assert_eq!(
+                get_formatted_date(date, "previous business day"),
+                "2024-03-19 00:00:00 000000000"
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod business_hours {
+        use crate::parse_datetime_at_date;
+        use chrono::{Local, TimeZone};
+
+        #[test]
+        fn test_business_hours() {
+            // a friday
+            let now = Local.with_ymd_and_hms(2024, 3, 15, 16, 0, 0).unwrap();
+            assert_eq!(
+                parse_datetime_at_date(now, "+2 business hours").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 18, 10, 0, 0).unwrap()
+            );
+            assert_eq!(
+                parse_datetime_at_date(now, "3 business hours ago").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 15, 13, 0, 0).unwrap()
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod weekend {
+        use crate::parse_datetime_at_date;
+        use chrono::{Local, TimeZone};
+
+        #[test]
+        fn test_weekend_from_midweek() {
+            // a wednesday
+            let now = Local.with_ymd_and_hms(2024, 3, 6, 13, 30, 0).unwrap();
+            let saturday = |day| Local.with_ymd_and_hms(2024, 3, day, 0, 0, 0).unwrap();
+
+            assert_eq!(
+                parse_datetime_at_date(now, "next weekend").unwrap(),
+                saturday(9)
+            );
+            assert_eq!(
+                parse_datetime_at_date(now, "this weekend").unwrap(),
+                saturday(9)
+            );
+            assert_eq!(
+                parse_datetime_at_date(now, "last weekend").unwrap(),
+                saturday(2)
+            );
+        }
+
+        #[test]
+        fn test_weekend_from_saturday() {
+            let now = Local.with_ymd_and_hms(2024, 3, 9, 13, 30, 0).unwrap();
+            let saturday = |day| Local.with_ymd_and_hms(2024, 3, day, 0, 0, 0).unwrap();
+
+            assert_eq!(
+                parse_datetime_at_date(now, "next weekend").unwrap(),
+                saturday(16)
+            );
+            assert_eq!(
+                parse_datetime_at_date(now, "this weekend").unwrap(),
+                saturday(9)
+            );
+            assert_eq!(
+                parse_datetime_at_date(now, "last weekend").unwrap(),
+                saturday(2)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod day_time {
+        use chrono::{DateTime, Local, TimeZone};
+
+        use crate::{parse_datetime_at_date, ParseDateTimeError};
+
+        fn get_formatted_date(date: DateTime<Local>, s: &str) -> String {
+            let result = parse_datetime_at_date(date, s).unwrap();
+
+            result.format("%F %T").to_string()
+        }
+
+        #[test]
+        fn test_day_at_time() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            assert_eq!(
+                get_formatted_date(date, "today at 08:30"),
+                "2024-03-15 08:30:00"
+            );
+            assert_eq!(
+                get_formatted_date(date, "tomorrow at 9:04:30 PM"),
+                "2024-03-16 21:04:30"
+            );
+            assert_eq!(
+                get_formatted_date(date, "yesterday at 23:15"),
+                "2024-03-14 23:15:00"
+            );
+            assert_eq!(
+                get_formatted_date(date, "tomorrow 08:30"),
+                "2024-03-16 08:30:00"
+            );
+        }
+
+        #[test]
+        fn test_now_at_time() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            assert_eq!(
+                parse_datetime_at_date(date, "now at 08:30"),
+                Err(ParseDateTimeError::InvalidInput)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod daypart {
+        use chrono::{Local, TimeZone};
+
+        use crate::parse_datetime_at_date;
+
+        #[test]
+        fn test_daypart() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            for (s, expected) in [
+                ("this morning", "2024-03-15 09:00:00"),
+                ("this afternoon", "2024-03-15 15:00:00"),
+                ("this evening", "2024-03-15 18:00:00"),
+                ("tonight", "2024-03-15 20:00:00"),
+                ("tomorrow morning", "2024-03-16 09:00:00"),
+                ("yesterday evening", "2024-03-14 18:00:00"),
+            ] {
+                let result = parse_datetime_at_date(date, s).unwrap();
+                assert_eq!(result.format("%F %T").to_string(), expected);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod midnight {
+        use chrono::{Local, TimeZone};
+
+        use crate::parse_datetime_at_date;
+
+        #[test]
+        fn test_midnight() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            let today = Local.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+            let tomorrow = Local.with_ymd_and_hms(2024, 3, 16, 0, 0, 0).unwrap();
+
+            // "midnight" is the start of today ...
+            assert_eq!(parse_datetime_at_date(date, "midnight").unwrap(), today);
+            assert_eq!(
+                parse_datetime_at_date(date, "today at midnight").unwrap(),
+                today
+            );
+
+            // ... whereas "midnight tonight" is the upcoming midnight
+            assert_eq!(
+                parse_datetime_at_date(date, "midnight tonight").unwrap(),
+                tomorrow
+            );
+            assert_eq!(
+                parse_datetime_at_date(date, "tomorrow midnight").unwrap(),
+                tomorrow
+            );
+        }
+
+        #[test]
+        fn test_empty_input() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            let today = Local.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+
+            // an empty string is the start of today, as with gnu date
+            for s in ["", "   ", "\t", " \t\n "] {
+                assert_eq!(parse_datetime_at_date(date, s).unwrap(), today, "{s:?}");
+            }
+        }
+
+        #[test]
+        fn test_noon_and_teatime() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+
+            assert_eq!(
+                parse_datetime_at_date(date, "noon").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap()
+            );
+            assert_eq!(
+                parse_datetime_at_date(date, "tomorrow at noon").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 16, 12, 0, 0).unwrap()
+            );
+            assert_eq!(
+                parse_datetime_at_date(date, "yesterday teatime").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 14, 16, 0, 0).unwrap()
+            );
+            assert_eq!(
+                parse_datetime_at_date(date, "2 hours after noon").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 15, 14, 0, 0).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_time_keyword_with_relative() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+
+            // a relative time applies to the keyword in either order
+            for (s, expected) in [
+                ("noon tomorrow", (2024, 3, 16, 12)),
+                ("tomorrow noon", (2024, 3, 16, 12)),
+                ("midnight + 1 day", (2024, 3, 16, 0)),
+                ("noon + 2 hours", (2024, 3, 15, 14)),
+                ("Teatime -1 hour", (2024, 3, 15, 15)),
+                ("+1 week midnight", (2024, 3, 22, 0)),
+            ] {
+                let (year, month, day, hour) = expected;
+                assert_eq!(
+                    parse_datetime_at_date(date, s).unwrap(),
+                    Local
+                        .with_ymd_and_hms(year, month, day, hour, 0, 0)
+                        .unwrap(),
+                    "{s}"
+                );
+            }
+
+            for s in ["noon foo", "noon noon", "noon + 2"] {
+                assert!(parse_datetime_at_date(date, s).is_err(), "{s}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod before_after {
+        use chrono::{Local, TimeZone};
+
+        use crate::{parse_datetime_at_date, ParseDateTimeError};
+
+        #[test]
+        fn test_relative_after_anchor() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            assert_eq!(
+                parse_datetime_at_date(date, "5 hours after midnight").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 15, 5, 0, 0).unwrap()
+            );
+            assert_eq!(
+                parse_datetime_at_date(date, "5 hours 30 minutes after midnight").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 15, 5, 30, 0).unwrap()
+            );
+            assert_eq!(
+                parse_datetime_at_date(date, "day after tomorrow").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 17, 10, 12, 3).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_relative_before_anchor() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            assert_eq!(
+                parse_datetime_at_date(date, "90 minutes before 12:00").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 15, 10, 30, 0).unwrap()
             );
-
             assert_eq!(
-                get_formatted_date(date, "sun"),
-                "2023-03-05 00:00:00 000000000"
+                parse_datetime_at_date(date, "90 minutes before midnight").unwrap(),
+                Local.with_ymd_and_hms(2024, 3, 14, 22, 30, 0).unwrap()
             );
         }
+
+        #[test]
+        fn test_connector_is_case_insensitive() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            for (s, expected) in [
+                (
+                    "5 hours AFTER midnight",
+                    Local.with_ymd_and_hms(2024, 3, 15, 5, 0, 0),
+                ),
+                (
+                    "5 hours After midnight",
+                    Local.with_ymd_and_hms(2024, 3, 15, 5, 0, 0),
+                ),
+                (
+                    "3 days Before tomorrow",
+                    Local.with_ymd_and_hms(2024, 3, 13, 10, 12, 3),
+                ),
+                (
+                    "The day BEFORE 2022-11-14",
+                    Local.with_ymd_and_hms(2022, 11, 13, 0, 0, 0),
+                ),
+            ] {
+                assert_eq!(
+                    parse_datetime_at_date(date, s).unwrap(),
+                    expected.unwrap(),
+                    "{s}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_relative_to_date() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            for (s, day) in [
+                ("the day before 2022-11-14", 13),
+                ("day before 2022-11-14", 13),
+                ("the day after 2022-11-14", 15),
+                ("day after 2022-11-14", 15),
+                ("2 days before 2022-11-14", 12),
+            ] {
+                assert_eq!(
+                    parse_datetime_at_date(date, s).unwrap(),
+                    Local.with_ymd_and_hms(2022, 11, day, 0, 0, 0).unwrap(),
+                    "{s}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_invalid_before_after() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+            for s in [
+                "5 hours after",
+                "after midnight",
+                "5 hours after foobar",
+                "foo after midnight",
+                "the after midnight",
+            ] {
+                assert_eq!(
+                    parse_datetime_at_date(date, s),
+                    Err(ParseDateTimeError::InvalidInput)
+                );
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod date_and_relative {
+        use chrono::{Local, SecondsFormat, TimeZone};
+
+        use crate::{parse_datetime_at_date, ParseDateTimeError};
+
+        #[test]
+        fn test_datetime_with_zone_and_relative() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+
+            // the zone is set before the relative time is applied, and doesn't
+            // take the sign of the relative time as an offset
+            for (s, expected) in [
+                (
+                    "2021-02-15 06:37:47 UTC +1 day",
+                    "2021-02-16T06:37:47+00:00",
+                ),
+                (
+                    "2021-02-15 06:37:47 UTC + 1 day",
+                    "2021-02-16T06:37:47+00:00",
+                ),
+                (
+                    "2021-02-15 06:37:47 UTC -1 day",
+                    "2021-02-14T06:37:47+00:00",
+                ),
+                (
+                    "1997-01-01 00:00:00 +0000 +1 year",
+                    "1998-01-01T00:00:00+00:00",
+                ),
+                (
+                    "2021-02-15 06:37:47 +0100 +1 day",
+                    "2021-02-16T06:37:47+01:00",
+                ),
+            ] {
+                assert_eq!(
+                    parse_datetime_at_date(date, s).unwrap().to_rfc3339(),
+                    expected,
+                    "{s}"
+                );
+            }
+
+            for s in [
+                "2021-02-15 06:37:47 UTC +1",
+                "2021-02-15 06:37:47 UTC +1 foo",
+                "2021-02-15 25:37:47 UTC +1 day",
+            ] {
+                assert_eq!(
+                    parse_datetime_at_date(date, s),
+                    Err(ParseDateTimeError::InvalidInput),
+                    "{s}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_utc_time_with_relative() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+
+            // the relative time is applied in UTC, the zone of the input, and
+            // the result stays in UTC rather than the local zone
+            for (s, expected) in [
+                ("2021-02-15T06:37:47Z +2 hours", "2021-02-15T08:37:47Z"),
+                ("2021-02-15T06:37:47Z -90 minutes", "2021-02-15T05:07:47Z"),
+                (
+                    "2021-02-15T06:37:47.123456Z +2 hours",
+                    "2021-02-15T08:37:47.123456Z",
+                ),
+                (
+                    "2021-02-15T06:37:47.123456Z -90 minutes",
+                    "2021-02-15T05:07:47.123456Z",
+                ),
+            ] {
+                let parsed = parse_datetime_at_date(date, s).unwrap();
+                assert_eq!(
+                    parsed.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                    expected,
+                    "{s}"
+                );
+                assert_eq!(parsed.offset().local_minus_utc(), 0, "{s}");
+            }
+        }
+
+        #[test]
+        fn test_date_with_relative() {
+            let date = Local.with_ymd_and_hms(2024, 3, 15, 10, 12, 3).unwrap();
+
+            // after a complete date, a number and a unit are a relative time
+            // rather than a year or a time, with or without a sign
+            for (s, expected) in [
+                ("2022-11-14 +3 months", "2023-02-14 00:00:00"),
+                ("2022-11-14 3 months", "2023-02-14 00:00:00"),
+                ("2022-11-14 -1 week", "2022-11-07 00:00:00"),
+                ("2022-11-14 next day", "2022-11-15 00:00:00"),
+                ("2022-11-14 10:00 2 hours ago", "2022-11-14 08:00:00"),
+            ] {
+                let parsed = parse_datetime_at_date(date, s).unwrap();
+                assert_eq!(parsed.format("%F %T").to_string(), expected, "{s}");
+            }
+
+            for s in ["2022-11-14 3", "2022-11-14 3 foo", "2022-11-14 +"] {
+                assert_eq!(
+                    parse_datetime_at_date(date, s),
+                    Err(ParseDateTimeError::InvalidInput),
+                    "{s}"
+                );
+            }
+        }
     }
 
     #[cfg(test)]
     mod timestamp {
-        use crate::parse_datetime;
-        use chrono::{TimeZone, Utc};
+        use crate::{parse_datetime, parse_timestamp, ParseDateTimeError};
+        use chrono::{Datelike, Duration, TimeZone, Utc};
 
         #[test]
         fn test_positive_and_negative_offsets() {
@@ -480,6 +2370,72 @@ mod tests {
                 assert_eq!(dt.unwrap(), time);
             }
         }
+
+        #[test]
+        fn test_whitespace() {
+            let time = Utc.timestamp_opt(1690466034, 0).unwrap();
+            for s in ["@ 1690466034", " @1690466034 ", "@\t1690466034"] {
+                assert_eq!(parse_datetime(s).unwrap(), time);
+            }
+
+            let time = Utc.timestamp_opt(-1690466034, 0).unwrap();
+            for s in ["@ - 1690466034", "@- 1690466034", " @ -1690466034 "] {
+                assert_eq!(parse_datetime(s).unwrap(), time);
+            }
+        }
+
+        #[test]
+        fn test_sub_second_units() {
+            assert_eq!(
+                parse_datetime("@1690466034123ms"),
+                parse_datetime("@1690466034.123")
+            );
+            assert_eq!(parse_datetime("@-1500000us"), parse_datetime("@-1.5"));
+        }
+
+        #[test]
+        fn test_before_epoch() {
+            let dt = parse_datetime("@-1234567890").unwrap();
+            assert_eq!(dt, Utc.with_ymd_and_hms(1930, 11, 18, 0, 28, 30).unwrap());
+            assert_eq!((dt.year(), dt.month(), dt.day()), (1930, 11, 18));
+
+            let dt = parse_datetime("@-1.5").unwrap();
+            assert_eq!(dt.timestamp(), -2);
+            assert_eq!(dt.timestamp_subsec_nanos(), 500_000_000);
+            assert_eq!(
+                dt,
+                Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 58).unwrap()
+                    + Duration::milliseconds(500)
+            );
+        }
+
+        #[test]
+        fn test_parse_timestamp() {
+            assert_eq!(
+                parse_timestamp("@1690466034").unwrap(),
+                Utc.timestamp_opt(1690466034, 0).unwrap()
+            );
+            assert_eq!(
+                parse_timestamp("@-1690466034").unwrap(),
+                Utc.timestamp_opt(-1690466034, 0).unwrap()
+            );
+            assert_eq!(
+                parse_timestamp("@1.25").unwrap(),
+                Utc.timestamp_opt(1, 250_000_000).unwrap()
+            );
+            assert_eq!(
+                parse_timestamp("@-1.5").unwrap(),
+                Utc.timestamp_opt(-2, 500_000_000).unwrap()
+            );
+
+            for s in ["1690466034", "@", "@0 +1 day", "now", "@99999999999999999"] {
+                assert_eq!(
+                    parse_timestamp(s),
+                    Err(ParseDateTimeError::InvalidInput),
+                    "{s}"
+                );
+            }
+        }
     }
 
     #[cfg(test)]
@@ -496,7 +2452,371 @@ mod tests {
                 .timestamp();
             assert_eq!(parsed_time, 1709480070)
         }
+
+        #[test]
+        fn test_time_correction() {
+            // the gnu compatibility cases for a time followed by signs
+            let test_date = Local.with_ymd_and_hms(2024, 3, 3, 0, 0, 0).unwrap();
+            for (correction, offset) in [
+                ("+", 0),
+                ("+-", 0),
+                ("+-01", -3600),
+                ("+-+++---++", 0),
+                ("+1-", 3600),
+                ("+--+1-+-", 3600),
+            ] {
+                let s = format!("12:34:56{correction}");
+                let parsed = parse_datetime_at_date(test_date, &s).unwrap();
+                assert_eq!(parsed.offset().local_minus_utc(), offset, "{s}");
+                assert_eq!(parsed.format("%T").to_string(), "12:34:56", "{s}");
+            }
+        }
+
+        #[test]
+        fn test_twelve_hour_time_with_zone() {
+            let test_date = Local.with_ymd_and_hms(2024, 3, 3, 0, 0, 0).unwrap();
+
+            // a zone abbreviation sets the offset of a 12-hour time
+            let parsed = parse_datetime_at_date(test_date, "8:30 PM EST").unwrap();
+            assert_eq!(parsed.to_rfc3339(), "2024-03-03T20:30:00-05:00");
+
+            // a numeric offset is accepted too, as in "9:04:30 PM +0530"
+            let parsed = parse_datetime_at_date(test_date, "8:30 PM +0500").unwrap();
+            assert_eq!(parsed.to_rfc3339(), "2024-03-03T20:30:00+05:00");
+        }
+    }
+
+    #[cfg(test)]
+    mod split {
+        use crate::parse_datetime_split;
+        use chrono::{FixedOffset, Local, NaiveDate, Offset, TimeZone};
+
+        #[test]
+        fn test_split_with_offset() {
+            let (naive, offset) = parse_datetime_split("2021-02-14 22:37:47 -0800").unwrap();
+            assert_eq!(
+                naive,
+                NaiveDate::from_ymd_opt(2021, 2, 14)
+                    .unwrap()
+                    .and_hms_opt(22, 37, 47)
+                    .unwrap()
+            );
+            assert_eq!(offset, FixedOffset::west_opt(8 * 3600).unwrap());
+        }
+
+        #[test]
+        fn test_split_with_utc_offset() {
+            let (naive, offset) = parse_datetime_split("202102150637UTC+0530").unwrap();
+            assert_eq!(
+                naive,
+                NaiveDate::from_ymd_opt(2021, 2, 15)
+                    .unwrap()
+                    .and_hms_opt(6, 37, 0)
+                    .unwrap()
+            );
+            assert_eq!(offset, FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap());
+        }
+
+        #[test]
+        fn test_split_without_offset() {
+            let (naive, offset) = parse_datetime_split("2021-02-15 06:37:47").unwrap();
+            let expected = Local.with_ymd_and_hms(2021, 2, 15, 6, 37, 47).unwrap();
+            assert_eq!(naive, expected.naive_local());
+            assert_eq!(offset, expected.offset().fix());
+        }
+    }
+
+    #[cfg(test)]
+    mod identity {
+        use crate::{parse_or_identity, ParseOutcome};
+        use chrono::{TimeZone, Utc};
+
+        #[test]
+        fn test_parsed() {
+            assert_eq!(
+                parse_or_identity("@1613371067"),
+                ParseOutcome::Parsed(Utc.timestamp_opt(1613371067, 0).unwrap().into())
+            );
+        }
+
+        #[test]
+        fn test_unchanged() {
+            let input = String::from("not a date");
+            match parse_or_identity(&input) {
+                ParseOutcome::Unchanged(s) => assert!(std::ptr::eq(s, input.as_str())),
+                ParseOutcome::Parsed(dt) => panic!("unexpectedly parsed as {dt}"),
+            }
+        }
     }
+
+    #[cfg(test)]
+    mod apply_relative {
+        use crate::{apply_relative, ParseDateTimeError};
+        use chrono::{NaiveDate, NaiveDateTime};
+
+        fn naive(year: i32, month: u32, day: u32, hour: u32) -> NaiveDateTime {
+            NaiveDate::from_ymd_opt(year, month, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+        }
+
+        #[test]
+        fn test_apply_relative() {
+            assert_eq!(
+                apply_relative(naive(2024, 3, 15, 10), "-2 hours").unwrap(),
+                naive(2024, 3, 15, 8)
+            );
+            assert_eq!(
+                apply_relative(naive(2024, 3, 15, 10), "3 weeks ago").unwrap(),
+                naive(2024, 2, 23, 10)
+            );
+        }
+
+        #[test]
+        fn test_apply_relative_month_overflow() {
+            assert_eq!(
+                apply_relative(naive(2024, 1, 31, 10), "+1 month").unwrap(),
+                naive(2024, 3, 2, 10)
+            );
+            assert_eq!(
+                apply_relative(naive(2024, 2, 29, 10), "+1 year").unwrap(),
+                naive(2025, 3, 1, 10)
+            );
+        }
+
+        #[test]
+        fn test_apply_relative_rejects_absolute() {
+            for s in ["2024-01-01", "@0", "10:00", "monday"] {
+                assert_eq!(
+                    apply_relative(naive(2024, 3, 15, 10), s),
+                    Err(ParseDateTimeError::InvalidInput)
+                );
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod end_of_day {
+        use crate::{parse_datetime_end_of_day, ParseDateTimeError};
+        use chrono::{Local, NaiveDate, TimeZone};
+
+        #[test]
+        fn test_date_only() {
+            let end_of_day = NaiveDate::from_ymd_opt(2022, 11, 14)
+                .unwrap()
+                .and_hms_nano_opt(23, 59, 59, 999_999_999)
+                .unwrap();
+            let expected = Local.from_local_datetime(&end_of_day).unwrap();
+            for s in ["2022-11-14", "20221114", "2022-W46-1"] {
+                assert_eq!(parse_datetime_end_of_day(s).unwrap(), expected, "{s}");
+            }
+        }
+
+        #[test]
+        fn test_explicit_time() {
+            assert_eq!(
+                parse_datetime_end_of_day("2022-11-14 10:00").unwrap(),
+                Local.with_ymd_and_hms(2022, 11, 14, 10, 0, 0).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_invalid() {
+            assert_eq!(
+                parse_datetime_end_of_day("foobar"),
+                Err(ParseDateTimeError::InvalidInput)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod relative_to {
+        use crate::{parse_datetime_relative_to, ParseDateTimeError};
+        use chrono::{Duration, TimeZone, Utc};
+
+        #[test]
+        fn test_relative_to_reference() {
+            let reference = Utc.with_ymd_and_hms(2022, 11, 14, 10, 0, 0).unwrap();
+            assert_eq!(
+                parse_datetime_relative_to(reference, "+1 hour").unwrap(),
+                reference + Duration::hours(1)
+            );
+            assert_eq!(
+                parse_datetime_relative_to(reference, "2 days ago").unwrap(),
+                reference - Duration::days(2)
+            );
+        }
+
+        #[test]
+        fn test_absolute_ignores_reference() {
+            let reference = Utc.with_ymd_and_hms(2022, 11, 14, 10, 0, 0).unwrap();
+            assert_eq!(
+                parse_datetime_relative_to(reference, "2021-02-14 06:37:47Z").unwrap(),
+                Utc.with_ymd_and_hms(2021, 2, 14, 6, 37, 47).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_invalid() {
+            let reference = Utc.with_ymd_and_hms(2022, 11, 14, 10, 0, 0).unwrap();
+            assert_eq!(
+                parse_datetime_relative_to(reference, "foobar"),
+                Err(ParseDateTimeError::InvalidInput)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod civil {
+        use crate::{parse_datetime_civil, ParseDateTimeError};
+        use chrono::NaiveDate;
+
+        #[test]
+        fn test_wall_clock_time() {
+            let expected = NaiveDate::from_ymd_opt(2021, 2, 14)
+                .unwrap()
+                .and_hms_opt(6, 37, 47)
+                .unwrap();
+            for s in [
+                "2021-02-14 06:37:47",
+                "2021-02-14T06:37:47",
+                "202102140637.47",
+            ] {
+                assert_eq!(parse_datetime_civil(s), Ok(expected), "{s}");
+            }
+        }
+
+        #[test]
+        fn test_date_only() {
+            let expected = NaiveDate::from_ymd_opt(2021, 2, 14)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap();
+            assert_eq!(parse_datetime_civil("2021-02-14"), Ok(expected));
+            assert_eq!(parse_datetime_civil("2021-W06-7"), Ok(expected));
+        }
+
+        #[test]
+        fn test_leap_second() {
+            let expected = NaiveDate::from_ymd_opt(2017, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap();
+            assert_eq!(parse_datetime_civil("2016-12-31 23:59:60"), Ok(expected));
+            assert_eq!(parse_datetime_civil("2016-12-31T23:59:60"), Ok(expected));
+        }
+
+        #[test]
+        fn test_rejects_offsets_and_relative_times() {
+            for s in [
+                "2021-02-14 06:37 +0100",
+                "2021-02-14 06:37:47Z",
+                "+1 day",
+                "@1613284667",
+                "foobar",
+            ] {
+                assert_eq!(
+                    parse_datetime_civil(s),
+                    Err(ParseDateTimeError::InvalidInput),
+                    "{s}"
+                );
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod recurrence {
+        use crate::{recurrence, ParseDateTimeError};
+        use chrono::{Duration, TimeZone, Utc};
+
+        #[test]
+        fn test_every_two_weeks() {
+            let base = Utc.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+            let occurrences = recurrence(base, "every 2 weeks", 4).unwrap();
+            assert_eq!(occurrences.len(), 4);
+            assert_eq!(occurrences[0], base + Duration::days(14));
+            for pair in occurrences.windows(2) {
+                assert_eq!(pair[1] - pair[0], Duration::days(14));
+            }
+        }
+
+        #[test]
+        fn test_every_month_does_not_drift() {
+            // each occurrence is computed from the base, so an end of month
+            // overflow doesn't carry into the following occurrences
+            let base = Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap();
+            assert_eq!(
+                recurrence(base, "every month", 4).unwrap(),
+                vec![
+                    Utc.with_ymd_and_hms(2024, 3, 2, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2024, 5, 31, 0, 0, 0).unwrap(),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_large_count() {
+            // a count above the limit is rejected up front
+            let base = Utc.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+            for count in [10_001, usize::MAX] {
+                assert_eq!(
+                    recurrence(base, "every day", count),
+                    Err(ParseDateTimeError::InvalidInput),
+                    "{count}"
+                );
+            }
+
+            // the occurrences run out of range before the count is reached
+            assert_eq!(
+                recurrence(base, "every 1000 years", 10_000),
+                Err(ParseDateTimeError::InvalidInput)
+            );
+        }
+
+        #[test]
+        fn test_invalid_recurrence() {
+            let base = Utc.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
+            for s in ["2 weeks", "every 2024-01-01", "every foobar", "every"] {
+                assert_eq!(
+                    recurrence(base, s, 2),
+                    Err(ParseDateTimeError::InvalidInput),
+                    "{s}"
+                );
+            }
+            assert_eq!(recurrence(base, "every day", 0), Ok(vec![]));
+        }
+    }
+
+    #[cfg(all(test, feature = "serde"))]
+    mod serde {
+        use regex::Error as RegexError;
+
+        use crate::ParseDateTimeError;
+
+        #[test]
+        fn test_round_trip() {
+            for err in [
+                ParseDateTimeError::InvalidRegex(RegexError::Syntax("unclosed group".to_owned())),
+                ParseDateTimeError::InvalidInput,
+                ParseDateTimeError::NegativeDuration,
+                ParseDateTimeError::AmbiguousCalendarUnit,
+            ] {
+                let json = serde_json::to_string(&err).unwrap();
+                assert_eq!(
+                    serde_json::from_str::<ParseDateTimeError>(&json).unwrap(),
+                    err
+                );
+            }
+            assert_eq!(
+                serde_json::to_string(&ParseDateTimeError::InvalidInput).unwrap(),
+                r#""InvalidInput""#
+            );
+        }
+    }
+
     /// Used to test example code presented in the README.
     mod readme_test {
         use crate::parse_datetime;
@@ -0,0 +1,236 @@
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::{digit1, multispace1, one_of};
+use nom::combinator::{all_consuming, map_res, opt, value};
+use nom::sequence::{preceded, terminated, tuple};
+use nom::{self, IResult};
+
+/// The hour at which a working day starts.
+const WORK_START_HOUR: u32 = 9;
+
+/// The hour at which a working day ends.
+const WORK_END_HOUR: u32 = 17;
+
+/// Parses "next business day", "previous business day" or "last business day"
+/// and returns the direction in days to step from the base date: `1` for
+/// forward and `-1` for backward.
+pub(crate) fn parse_business_day(s: &str) -> Option<i64> {
+    let s = s.trim();
+
+    let parse_result: IResult<&str, (i64, &str, &str, &str, &str)> = all_consuming(tuple((
+        alt((
+            value(1, tag_no_case("next")),
+            value(-1, alt((tag_no_case("previous"), tag_no_case("last")))),
+        )),
+        multispace1,
+        tag_no_case("business"),
+        multispace1,
+        tag_no_case("day"),
+    )))(s);
+
+    match parse_result {
+        Ok((_, (step, ..))) => Some(step),
+        Err(_) => None,
+    }
+}
+
+/// Parses a number of business hours such as "+4 business hours" or
+/// "1 business hour ago" and returns the signed number of hours.
+pub(crate) fn parse_business_hours(s: &str) -> Option<i64> {
+    let s = s.trim();
+
+    let parse_result: IResult<&str, (Option<char>, i64, Option<&str>)> = all_consuming(tuple((
+        opt(one_of("+-")),
+        terminated(
+            map_res(digit1, str::parse),
+            tuple((
+                multispace1,
+                tag_no_case("business"),
+                multispace1,
+                alt((tag_no_case("hours"), tag_no_case("hour"))),
+            )),
+        ),
+        opt(preceded(multispace1, tag_no_case("ago"))),
+    )))(s);
+
+    match parse_result {
+        Ok((_, (sign, hours, ago))) => {
+            let negative = (sign == Some('-')) != ago.is_some();
+            Some(if negative { -hours } else { hours })
+        }
+        Err(_) => None,
+    }
+}
+
+/// Adds a number of business hours to a date and time, counting only the
+/// hours between 09:00 and 17:00 on business days.
+///
+/// A date and time outside of working hours is first moved to the start of
+/// the next working period, or the end of the previous one when going
+/// backwards. The hours are then used up one working day at a time, so
+/// Friday 16:00 plus two business hours is Monday 10:00.
+pub(crate) fn add_business_hours(dt: NaiveDateTime, hours: i64) -> Option<NaiveDateTime> {
+    let start = NaiveTime::from_hms_opt(WORK_START_HOUR, 0, 0)?;
+    let end = NaiveTime::from_hms_opt(WORK_END_HOUR, 0, 0)?;
+
+    // The end of the working period in the direction of travel, and where
+    // the next working period begins
+    let step = if hours < 0 { -1 } else { 1 };
+    let (towards, from) = if step > 0 { (end, start) } else { (start, end) };
+    let hours = hours.checked_abs()?;
+    let mut current = dt;
+    if !is_business_day(current.weekday())
+        || (step > 0 && current.time() >= end)
+        || (step < 0 && current.time() <= start)
+    {
+        current = next_business_day(current.date(), step)?.and_time(from);
+    } else if current.time() < start || current.time() > end {
+        current = current.date().and_time(from);
+    }
+
+    // A full week has the same working hours wherever it starts, so skip whole
+    // weeks directly, leaving at least one hour to land within a working day
+    let week_hours = i64::from(WORK_END_HOUR - WORK_START_HOUR) * 5;
+    let weeks = hours.saturating_sub(1) / week_hours;
+    current = current.checked_add_signed(Duration::try_weeks(weeks * step)?)?;
+    let mut remaining = Duration::try_hours(hours - weeks * week_hours)?;
+
+    loop {
+        let available = (current.date().and_time(towards) - current).abs();
+        if remaining <= available {
+            return current.checked_add_signed(if step > 0 { remaining } else { -remaining });
+        }
+        remaining -= available;
+        current = next_business_day(current.date(), step)?.and_time(from);
+    }
+}
+
+fn next_business_day(date: NaiveDate, step: i64) -> Option<NaiveDate> {
+    let mut date = date;
+    loop {
+        date = date.checked_add_signed(Duration::try_days(step)?)?;
+        if is_business_day(date.weekday()) {
+            return Some(date);
+        }
+    }
+}
+
+/// Business days are Monday through Friday.
+pub(crate) fn is_business_day(weekday: Weekday) -> bool {
+    !matches!(weekday, Weekday::Sat | Weekday::Sun)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use chrono::{NaiveDate, NaiveDateTime, Weekday::*};
+
+    use crate::parse_business_day::{
+        add_business_hours, is_business_day, parse_business_day, parse_business_hours,
+    };
+
+    fn at(day: u32, hour: u32, min: u32) -> NaiveDateTime {
+        // March 2024, where the 15th is a friday
+        NaiveDate::from_ymd_opt(2024, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_valid_business_day() {
+        assert_eq!(parse_business_day("next business day"), Some(1));
+        assert_eq!(parse_business_day("previous business day"), Some(-1));
+        assert_eq!(parse_business_day("last business day"), Some(-1));
+        assert_eq!(parse_business_day(" Next  Business Day "), Some(1));
+    }
+
+    #[test]
+    fn test_invalid_business_day() {
+        for s in [
+            "business day",
+            "next business",
+            "next businessday",
+            "this business day",
+            "next business days",
+            "next day",
+        ] {
+            assert!(parse_business_day(s).is_none());
+        }
+    }
+
+    #[test]
+    fn test_is_business_day() {
+        for weekday in [Mon, Tue, Wed, Thu, Fri] {
+            assert!(is_business_day(weekday));
+        }
+        assert!(!is_business_day(Sat));
+        assert!(!is_business_day(Sun));
+    }
+
+    #[test]
+    fn test_valid_business_hours() {
+        assert_eq!(parse_business_hours("+4 business hours"), Some(4));
+        assert_eq!(parse_business_hours("4 business hours"), Some(4));
+        assert_eq!(parse_business_hours("1 Business Hour"), Some(1));
+        assert_eq!(parse_business_hours("-2 business hours"), Some(-2));
+        assert_eq!(parse_business_hours("2 business hours ago"), Some(-2));
+        assert_eq!(parse_business_hours("-2 business hours ago"), Some(2));
+    }
+
+    #[test]
+    fn test_invalid_business_hours() {
+        for s in [
+            "business hours",
+            "+4 business",
+            "4 hours",
+            "4 businesshours",
+            "four business hours",
+        ] {
+            assert!(parse_business_hours(s).is_none());
+        }
+    }
+
+    #[test]
+    fn test_add_business_hours_within_day() {
+        assert_eq!(add_business_hours(at(13, 10, 30), 4), Some(at(13, 14, 30)));
+        assert_eq!(add_business_hours(at(13, 10, 0), 7), Some(at(13, 17, 0)));
+        assert_eq!(add_business_hours(at(13, 14, 0), -3), Some(at(13, 11, 0)));
+    }
+
+    #[test]
+    fn test_add_business_hours_spills_into_next_day() {
+        assert_eq!(add_business_hours(at(13, 16, 0), 2), Some(at(14, 10, 0)));
+        assert_eq!(add_business_hours(at(13, 10, 0), 16), Some(at(15, 10, 0)));
+        assert_eq!(add_business_hours(at(13, 10, 0), -2), Some(at(12, 16, 0)));
+    }
+
+    #[test]
+    fn test_add_business_hours_skips_weekend() {
+        // friday 16:00 plus two hours is monday 10:00
+        assert_eq!(add_business_hours(at(15, 16, 0), 2), Some(at(18, 10, 0)));
+        assert_eq!(add_business_hours(at(18, 10, 0), -2), Some(at(15, 16, 0)));
+    }
+
+    #[test]
+    fn test_add_business_hours_whole_weeks() {
+        assert_eq!(add_business_hours(at(11, 9, 0), 40), Some(at(15, 17, 0)));
+        assert_eq!(add_business_hours(at(11, 9, 0), 41), Some(at(18, 10, 0)));
+        assert_eq!(add_business_hours(at(13, 12, 0), 80), Some(at(27, 12, 0)));
+        assert_eq!(add_business_hours(at(27, 12, 0), -80), Some(at(13, 12, 0)));
+        assert!(add_business_hours(at(13, 12, 0), i64::MAX).is_none());
+    }
+
+    #[test]
+    fn test_add_business_hours_outside_working_hours() {
+        // outside of working hours counting starts at the next working period
+        assert_eq!(add_business_hours(at(13, 7, 0), 1), Some(at(13, 10, 0)));
+        assert_eq!(add_business_hours(at(13, 20, 0), 1), Some(at(14, 10, 0)));
+        assert_eq!(add_business_hours(at(16, 12, 0), 1), Some(at(18, 10, 0)));
+        assert_eq!(add_business_hours(at(13, 20, 0), -1), Some(at(13, 16, 0)));
+        assert_eq!(add_business_hours(at(18, 7, 0), -1), Some(at(15, 16, 0)));
+    }
+}
@@ -0,0 +1,86 @@
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::{digit1, multispace1};
+use nom::combinator::{all_consuming, map_res, opt};
+use nom::sequence::{pair, terminated, tuple};
+use nom::{self, IResult};
+
+/// Parses a day of the month written as an ordinal, e.g. "3rd" or "the 15th",
+/// and returns the day.
+///
+/// The suffix has to match the number, so "3th" is rejected.
+pub(crate) fn parse_day_of_month(s: &str) -> Option<u32> {
+    let s = s.trim();
+
+    let parse_result: IResult<&str, (Option<&str>, (u32, &str))> = all_consuming(tuple((
+        opt(terminated(tag_no_case("the"), multispace1)),
+        pair(
+            map_res(digit1, str::parse::<u32>),
+            alt((
+                tag_no_case("st"),
+                tag_no_case("nd"),
+                tag_no_case("rd"),
+                tag_no_case("th"),
+            )),
+        ),
+    )))(s);
+
+    match parse_result {
+        Ok((_, (_, (day, suffix))))
+            if (1..=31).contains(&day) && suffix.eq_ignore_ascii_case(suffix_for(day)) =>
+        {
+            Some(day)
+        }
+        _ => None,
+    }
+}
+
+fn suffix_for(day: u32) -> &'static str {
+    match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::parse_day_of_month::parse_day_of_month;
+
+    #[test]
+    fn test_valid_days() {
+        let days = [
+            ("1st", 1),
+            ("2nd", 2),
+            ("3rd", 3),
+            ("4th", 4),
+            ("11th", 11),
+            ("12th", 12),
+            ("13th", 13),
+            ("21st", 21),
+            ("22nd", 22),
+            ("23rd", 23),
+            ("31st", 31),
+            ("the 3rd", 3),
+            (" The  15TH ", 15),
+        ];
+
+        for (name, day) in days {
+            assert_eq!(parse_day_of_month(name), Some(day));
+        }
+    }
+
+    #[test]
+    fn test_invalid_days() {
+        for s in [
+            "0th", "32nd", "3th", "11st", "22th", "the", "the3rd", "3", "third",
+        ] {
+            assert!(parse_day_of_month(s).is_none());
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::multispace1;
+use nom::combinator::value;
+use nom::sequence::{delimited, terminated};
+use nom::{self, IResult};
+
+/// Splits a string such as "tomorrow at 10:00" or "today 9:30 PM" into the
+/// day shift and the remaining time string.
+///
+/// The day shift is the number of days relative to the base date, or `None`
+/// for "now", which already implies a time of day and thus cannot be combined
+/// with another time.
+pub(crate) fn parse_day_time(s: &str) -> Option<(Option<i64>, &str)> {
+    let parse_result: IResult<&str, Option<i64>> = terminated(
+        alt((
+            value(Some(0), tag_no_case("today")),
+            value(Some(1), tag_no_case("tomorrow")),
+            value(Some(-1), tag_no_case("yesterday")),
+            value(None, tag_no_case("now")),
+        )),
+        alt((
+            delimited(multispace1, tag_no_case("at"), multispace1),
+            multispace1,
+        )),
+    )(s.trim());
+
+    match parse_result {
+        Ok((time, days)) => Some((days, time)),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::parse_day_time::parse_day_time;
+
+    #[test]
+    fn test_valid_day_time() {
+        assert_eq!(parse_day_time("today at 10:00"), Some((Some(0), "10:00")));
+        assert_eq!(
+            parse_day_time("Tomorrow AT 10:00"),
+            Some((Some(1), "10:00"))
+        );
+        assert_eq!(
+            parse_day_time(" yesterday 9:30 PM "),
+            Some((Some(-1), "9:30 PM"))
+        );
+        assert_eq!(parse_day_time("now at 10:00"), Some((None, "10:00")));
+    }
+
+    #[test]
+    fn test_invalid_day_time() {
+        for s in ["today", "todayat 10:00", "10:00 today", "next day"] {
+            assert!(parse_day_time(s).is_none());
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use chrono::NaiveTime;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::multispace1;
+use nom::combinator::{all_consuming, value};
+use nom::sequence::{separated_pair, tuple};
+use nom::{self, IResult};
+
+/// Parses a part of the day such as "this morning", "tomorrow evening" or
+/// "tonight" and returns the day shift relative to the base date together
+/// with the time of day it stands for.
+///
+/// The parts of the day map to the following times:
+///
+/// * "morning" - 09:00
+/// * "afternoon" - 15:00
+/// * "evening" - 18:00
+/// * "night" and "tonight" - 20:00
+///
+/// "midnight tonight" is the upcoming midnight, i.e. the start of the next
+/// day, whereas "midnight" on its own is the start of the base date.
+pub(crate) fn parse_daypart(s: &str) -> Option<(i64, NaiveTime)> {
+    let s = s.trim();
+
+    let parse_result: IResult<&str, (i64, u32)> = all_consuming(alt((
+        value((0, 20), tag_no_case("tonight")),
+        value(
+            (1, 0),
+            tuple((tag_no_case("midnight"), multispace1, tag_no_case("tonight"))),
+        ),
+        separated_pair(
+            alt((
+                value(0, tag_no_case("this")),
+                value(1, tag_no_case("tomorrow")),
+                value(-1, tag_no_case("yesterday")),
+            )),
+            multispace1,
+            alt((
+                value(9, tag_no_case("morning")),
+                value(15, tag_no_case("afternoon")),
+                value(18, tag_no_case("evening")),
+                value(20, tag_no_case("night")),
+            )),
+        ),
+    )))(s);
+
+    match parse_result {
+        Ok((_, (days, hour))) => Some((days, NaiveTime::from_hms_opt(hour, 0, 0)?)),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use chrono::NaiveTime;
+
+    use crate::parse_daypart::parse_daypart;
+
+    fn time(hour: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_valid_dayparts() {
+        let dayparts = [
+            ("this morning", (0, time(9))),
+            ("this afternoon", (0, time(15))),
+            ("this evening", (0, time(18))),
+            ("tonight", (0, time(20))),
+            ("tomorrow morning", (1, time(9))),
+            ("yesterday night", (-1, time(20))),
+            (" This  Evening ", (0, time(18))),
+            ("midnight tonight", (1, time(0))),
+        ];
+
+        for (name, expected) in dayparts {
+            assert_eq!(parse_daypart(name), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_invalid_dayparts() {
+        for s in [
+            "morning",
+            "this",
+            "next morning",
+            "tomorrow tonight",
+            "nightly",
+            "midnight",
+            "tonight midnight",
+        ] {
+            assert!(parse_daypart(s).is_none());
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use nom::character::complete::{alpha1, char};
+use nom::combinator::{map_opt, opt, recognize};
+use nom::sequence::pair;
+use nom::{self, IResult};
+
+/// Ordinal words and their values, e.g. "third tuesday" or "twentieth week".
+///
+/// Like gnu date, "second" is left out, as it is the unit.
+pub(crate) const ORDINALS: &[(&str, i64)] = &[
+    ("first", 1),
+    ("third", 3),
+    ("fourth", 4),
+    ("fifth", 5),
+    ("sixth", 6),
+    ("seventh", 7),
+    ("eighth", 8),
+    ("ninth", 9),
+    ("tenth", 10),
+    ("eleventh", 11),
+    ("twelfth", 12),
+    ("thirteenth", 13),
+    ("fourteenth", 14),
+    ("fifteenth", 15),
+    ("sixteenth", 16),
+    ("seventeenth", 17),
+    ("eighteenth", 18),
+    ("nineteenth", 19),
+    ("twentieth", 20),
+    ("twenty-first", 21),
+    ("twenty-second", 22),
+    ("twenty-third", 23),
+    ("twenty-fourth", 24),
+    ("twenty-fifth", 25),
+    ("twenty-sixth", 26),
+    ("twenty-seventh", 27),
+    ("twenty-eighth", 28),
+    ("twenty-ninth", 29),
+    ("thirtieth", 30),
+    ("thirty-first", 31),
+];
+
+/// Returns the value of an ordinal word such as "third" or "twenty-first".
+pub(crate) fn ordinal_value(word: &str) -> Option<i64> {
+    ORDINALS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(word))
+        .map(|&(_, value)| value)
+}
+
+/// Parses an ordinal word such as "third" or "twenty-first".
+pub(crate) fn ordinal(s: &str) -> IResult<&str, i64> {
+    map_opt(
+        recognize(pair(alpha1, opt(pair(char('-'), alpha1)))),
+        ordinal_value,
+    )(s)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::parse_ordinal::{ordinal, ordinal_value};
+
+    #[test]
+    fn test_ordinal_value() {
+        for (word, value) in [
+            ("first", 1),
+            ("twelfth", 12),
+            ("thirteenth", 13),
+            ("twentieth", 20),
+            ("twenty-second", 22),
+            ("Thirty-First", 31),
+        ] {
+            assert_eq!(ordinal_value(word), Some(value), "{word}");
+        }
+
+        for word in ["second", "zeroth", "thirty-second", "twenty first", "3rd"] {
+            assert_eq!(ordinal_value(word), None, "{word}");
+        }
+    }
+
+    #[test]
+    fn test_ordinal() {
+        assert_eq!(ordinal("thirteenth tuesday"), Ok((" tuesday", 13)));
+        assert_eq!(ordinal("twenty-first week"), Ok((" week", 21)));
+        assert!(ordinal("second week").is_err());
+        assert!(ordinal("tuesday").is_err());
+    }
+}
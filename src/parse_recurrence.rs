@@ -0,0 +1,40 @@
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::multispace1;
+use nom::sequence::terminated;
+use nom::{self, IResult};
+
+/// Parses a recurrence such as "every 2 weeks" and returns the relative time
+/// between two occurrences, e.g. "2 weeks".
+pub(crate) fn parse_recurrence(s: &str) -> Option<&str> {
+    let parse_result: IResult<&str, &str> = terminated(tag_no_case("every"), multispace1)(s.trim());
+
+    match parse_result {
+        Ok((relative, _)) => Some(relative),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::parse_recurrence::parse_recurrence;
+
+    #[test]
+    fn test_valid_recurrence() {
+        assert_eq!(parse_recurrence("every 2 weeks"), Some("2 weeks"));
+        assert_eq!(parse_recurrence(" Every  month "), Some("month"));
+        assert_eq!(
+            parse_recurrence("every 1 day and 2 hours"),
+            Some("1 day and 2 hours")
+        );
+    }
+
+    #[test]
+    fn test_invalid_recurrence() {
+        for s in ["every", "everyday", "2 weeks", "each week"] {
+            assert!(parse_recurrence(s).is_none());
+        }
+    }
+}
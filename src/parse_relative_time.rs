@@ -1,8 +1,32 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
+use crate::parse_ordinal::{ordinal_value, ORDINALS};
 use crate::ParseDateTimeError;
-use chrono::{DateTime, Days, Duration, Months, TimeZone};
+use chrono::{DateTime, Datelike, Days, Duration, NaiveDate, TimeZone, Utc};
 use regex::Regex;
+use std::sync::LazyLock;
+
+// A leading qualifier such as "about 3 hours" doesn't change the time
+static QUALIFIER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^(?:about|exactly|roughly|approximately)\s+").unwrap());
+
+// The "in" prefix and "from now" suffix of a relative time in the future
+static IN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^in\s+").unwrap());
+static FROM_NOW: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\s+from\s+now$").unwrap());
+
+static TIME_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    let ordinals = ORDINALS.iter().map(|(word, _)| *word).collect::<Vec<_>>();
+    Regex::new(&format!(
+        r"(?x)
+        (?:(?P<value>[-+]?\d*|(?i:an?)\b|(?i:{})\b)\s*)?
+        (\s*(?P<direction>next|this|last)?\s*)?
+        (?P<unit>years?|quarters?|months?|fortnights?|weeks?|days?|hours?|h|minutes?|mins?|m|seconds?|secs?|s|yesterday|tomorrow|now|today)
+        (\s*(?P<separator>and|,)?\s*)?
+        (\s*(?P<ago>ago)?)?",
+        ordinals.join("|")
+    ))
+    .unwrap()
+});
 
 /// Parses a relative time string and adds the duration that it represents to the
 /// given date.
@@ -22,10 +46,11 @@ use regex::Regex;
 /// * "yesterday"
 /// * "tomorrow"
 /// * use "ago" for the past
+/// * "in" `num` `unit` or `num` `unit` "from now" for the future (e.g., "in 3 days")
 ///
-/// `[num]` can be a positive or negative integer.
-/// [unit] can be one of the following: "fortnight", "week", "day", "hour",
-/// "minute", "min", "second", "sec" and their plural forms.
+/// `[num]` can be a positive or negative integer, or "a"/"an" for one.
+/// [unit] can be one of the following: "year", "quarter", "month", "fortnight",
+/// "week", "day", "hour", "minute", "min", "second", "sec" and their plural forms.
 ///
 /// It is also possible to pass "1 hour 2 minutes" or "2 days and 2 hours"
 ///
@@ -40,36 +65,63 @@ use regex::Regex;
 /// cannot be parsed as a relative time.
 ///
 pub fn parse_relative_time_at_date<T: TimeZone>(
+    datetime: DateTime<T>,
+    s: &str,
+) -> Result<DateTime<T>, ParseDateTimeError> {
+    parse_relative_time_times_at_date(datetime, s, 1)
+}
+
+/// Parses a relative time string and adds the duration that it represents
+/// `times` times over to the given date.
+///
+/// Each value is multiplied rather than the relative time being applied
+/// repeatedly, so "month" three times from January 31 is April 30, not
+/// March 28.
+pub(crate) fn parse_relative_time_times_at_date<T: TimeZone>(
     mut datetime: DateTime<T>,
     s: &str,
+    times: i64,
 ) -> Result<DateTime<T>, ParseDateTimeError> {
-    let time_pattern: Regex = Regex::new(
-        r"(?x)
-        (?:(?P<value>[-+]?\d*)\s*)?
-        (\s*(?P<direction>next|this|last)?\s*)?
-        (?P<unit>years?|months?|fortnights?|weeks?|days?|hours?|h|minutes?|mins?|m|seconds?|secs?|s|yesterday|tomorrow|now|today)
-        (\s*(?P<separator>and|,)?\s*)?
-        (\s*(?P<ago>ago)?)?",
-    )?;
+    let s = QUALIFIER.find(s).map_or(s, |m| &s[m.end()..]);
+
+    // "in 3 days" and "3 days from now" are the same as "3 days", and can't be
+    // combined with "ago"
+    let s = match IN
+        .find(s)
+        .map(|m| &s[m.end()..])
+        .or_else(|| FROM_NOW.find(s).map(|m| &s[..m.start()]))
+    {
+        Some(_) if s.contains(" ago") => return Err(ParseDateTimeError::InvalidInput),
+        Some(stripped) => stripped,
+        None => s,
+    };
 
     let mut is_ago = s.contains(" ago");
     let mut captures_processed = 0;
     let mut total_length = 0;
 
-    for capture in time_pattern.captures_iter(s) {
+    for capture in TIME_PATTERN.captures_iter(s) {
         captures_processed += 1;
 
         let value_str = capture
             .name("value")
             .ok_or(ParseDateTimeError::InvalidInput)?
             .as_str();
-        let value = if value_str.is_empty() {
+        let value = if value_str.is_empty()
+            || value_str.eq_ignore_ascii_case("a")
+            || value_str.eq_ignore_ascii_case("an")
+        {
             1
+        } else if let Some(value) = ordinal_value(value_str) {
+            value
         } else {
             value_str
                 .parse::<i64>()
                 .map_err(|_| ParseDateTimeError::InvalidInput)?
         };
+        let value = value
+            .checked_mul(times)
+            .ok_or(ParseDateTimeError::InvalidInput)?;
 
         let direction = capture.name("direction").map_or("", |d| d.as_str());
 
@@ -91,6 +143,7 @@ pub fn parse_relative_time_at_date<T: TimeZone>(
         } else {
             match unit {
                 "years" | "year" => add_months(datetime, value * 12, is_ago),
+                "quarters" | "quarter" => add_months(datetime, value * 3, is_ago),
                 "months" | "month" => add_months(datetime, value, is_ago),
                 "fortnights" | "fortnight" => add_days(datetime, value * 14, is_ago),
                 "weeks" | "week" => add_days(datetime, value * 7, is_ago),
@@ -102,8 +155,8 @@ pub fn parse_relative_time_at_date<T: TimeZone>(
                 "seconds" | "second" | "secs" | "sec" | "s" => {
                     add_duration(datetime, Duration::seconds(value), is_ago)
                 }
-                "yesterday" => add_days(datetime, 1, true),
-                "tomorrow" => add_days(datetime, 1, false),
+                "yesterday" => add_days(datetime, times, true),
+                "tomorrow" => add_days(datetime, times, false),
                 "now" | "today" => Some(datetime),
                 _ => None,
             }
@@ -131,22 +184,58 @@ pub fn parse_relative_time_at_date<T: TimeZone>(
     }
 }
 
+/// Parses a relative time string and returns the `std::time::Duration` that it
+/// represents.
+///
+/// Years, quarters and months do not have a fixed length, so they are rejected
+/// with `ParseDateTimeError::AmbiguousCalendarUnit`. A `std::time::Duration`
+/// cannot be negative, so a relative time in the past is rejected with
+/// `ParseDateTimeError::NegativeDuration`.
+pub fn parse_std_duration(s: &str) -> Result<std::time::Duration, ParseDateTimeError> {
+    for capture in TIME_PATTERN.captures_iter(s) {
+        if let Some(unit) = capture.name("unit") {
+            if matches!(
+                unit.as_str(),
+                "years" | "year" | "quarters" | "quarter" | "months" | "month"
+            ) {
+                return Err(ParseDateTimeError::AmbiguousCalendarUnit);
+            }
+        }
+    }
+
+    let base = DateTime::<Utc>::UNIX_EPOCH;
+    let datetime = parse_relative_time_at_date(base, s)?;
+    (datetime - base)
+        .to_std()
+        .map_err(|_| ParseDateTimeError::NegativeDuration)
+}
+
 fn add_months<T: TimeZone>(
     datetime: DateTime<T>,
     months: i64,
-    mut is_ago: bool,
+    is_ago: bool,
 ) -> Option<DateTime<T>> {
-    let months = if months < 0 {
-        is_ago = !is_ago;
-        u32::try_from(-months).ok()?
+    let months = if is_ago {
+        months.checked_neg()?
     } else {
-        u32::try_from(months).ok()?
+        months
     };
-    if is_ago {
-        datetime.checked_sub_months(Months::new(months))
-    } else {
-        datetime.checked_add_months(Months::new(months))
-    }
+    let months = i64::from(datetime.year())
+        .checked_mul(12)?
+        .checked_add(i64::from(datetime.month0()))?
+        .checked_add(months)?;
+    let year = i32::try_from(months.div_euclid(12)).ok()?;
+    let month = u32::try_from(months.rem_euclid(12)).ok()? + 1;
+
+    // Like gnu date, a day past the end of the resulting month rolls over into
+    // the following month rather than being clamped, e.g. 1996-02-29 plus one
+    // year is 1997-03-01
+    let date = NaiveDate::from_ymd_opt(year, month, 1)?
+        .checked_add_days(Days::new(u64::from(datetime.day0())))?;
+    datetime
+        .timezone()
+        .from_local_datetime(&date.and_time(datetime.time()))
+        .single()
 }
 
 fn add_days<T: TimeZone>(
@@ -179,8 +268,13 @@ fn add_duration<T: TimeZone>(
 #[cfg(test)]
 mod tests {
     use super::parse_relative_time_at_date;
+    use super::parse_relative_time_times_at_date;
+    use super::parse_std_duration;
     use super::ParseDateTimeError;
-    use chrono::{Days, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+    use chrono::{
+        DateTime, Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+        Utc,
+    };
 
     fn parse_duration(s: &str) -> Result<Duration, ParseDateTimeError> {
         let now = Utc::now();
@@ -188,9 +282,15 @@ mod tests {
         Ok(parsed - now)
     }
 
+    /// The current time moved to the middle of its month, where adding months
+    /// never runs past the end of a month.
+    fn mid_month_now() -> DateTime<Utc> {
+        Utc::now().with_day(15).unwrap()
+    }
+
     #[test]
     fn test_years() {
-        let now = Utc::now();
+        let now = mid_month_now();
         assert_eq!(
             parse_relative_time_at_date(now, "1 year").unwrap(),
             now.checked_add_months(Months::new(12)).unwrap()
@@ -213,9 +313,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_quarters() {
+        let now = mid_month_now();
+        assert_eq!(
+            parse_relative_time_at_date(now, "1 quarter").unwrap(),
+            now.checked_add_months(Months::new(3)).unwrap()
+        );
+        assert_eq!(
+            parse_relative_time_at_date(now, "+2 quarters").unwrap(),
+            now.checked_add_months(Months::new(6)).unwrap()
+        );
+        assert_eq!(
+            parse_relative_time_at_date(now, "1 quarter ago").unwrap(),
+            now.checked_sub_months(Months::new(3)).unwrap()
+        );
+        assert_eq!(
+            parse_relative_time_at_date(now, "last quarter").unwrap(),
+            now.checked_sub_months(Months::new(3)).unwrap()
+        );
+        assert_eq!(
+            parse_relative_time_at_date(now, "quarter").unwrap(),
+            now.checked_add_months(Months::new(3)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_quarter_month_overflow() {
+        // A quarter follows the same end-of-month rule as three months
+        let now = Utc.from_utc_datetime(&NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        ));
+        assert_eq!(
+            parse_relative_time_at_date(now, "+1 quarter").unwrap(),
+            parse_relative_time_at_date(now, "+3 months").unwrap()
+        );
+        assert_eq!(
+            parse_relative_time_at_date(now, "+1 quarter")
+                .unwrap()
+                .date_naive(),
+            NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_month_rollover() {
+        let at = |year, month, day| {
+            Utc.from_utc_datetime(&NaiveDateTime::new(
+                NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+                NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            ))
+        };
+
+        // gnu date rolls a day past the end of the month over into the next
+        // month instead of clamping it
+        assert_eq!(
+            parse_relative_time_at_date(at(1996, 2, 29), "1 year").unwrap(),
+            at(1997, 3, 1)
+        );
+        assert_eq!(
+            parse_relative_time_at_date(at(1996, 2, 29), "4 years").unwrap(),
+            at(2000, 2, 29)
+        );
+        assert_eq!(
+            parse_relative_time_at_date(at(2024, 1, 31), "1 month").unwrap(),
+            at(2024, 3, 2)
+        );
+        assert_eq!(
+            parse_relative_time_at_date(at(2024, 3, 31), "1 month ago").unwrap(),
+            at(2024, 3, 2)
+        );
+        assert_eq!(
+            parse_relative_time_at_date(at(2024, 12, 31), "-13 months").unwrap(),
+            at(2023, 12, 1)
+        );
+    }
+
     #[test]
     fn test_months() {
-        let now = Utc::now();
+        let now = mid_month_now();
         assert_eq!(
             parse_relative_time_at_date(now, "1 month").unwrap(),
             now.checked_add_months(Months::new(1)).unwrap()
@@ -354,9 +531,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_in_and_from_now() {
+        let now = mid_month_now();
+        assert_eq!(
+            parse_relative_time_at_date(now, "in 3 days").unwrap(),
+            now + Duration::days(3)
+        );
+        assert_eq!(
+            parse_relative_time_at_date(now, "3 days from now").unwrap(),
+            now + Duration::days(3)
+        );
+        assert_eq!(
+            parse_relative_time_at_date(now, "in 1 hour 30 minutes").unwrap(),
+            now + Duration::minutes(90)
+        );
+        assert_eq!(
+            parse_relative_time_at_date(now, "in 1 year 2 months").unwrap(),
+            now.checked_add_months(Months::new(14)).unwrap()
+        );
+        assert_eq!(
+            parse_relative_time_at_date(now, "2 weeks and 1 day from now").unwrap(),
+            now + Duration::days(15)
+        );
+
+        // "in" and "from now" are matched case-insensitively
+        for s in [
+            "In 3 days",
+            "IN 3 days",
+            "3 days From Now",
+            "3 days FROM NOW",
+        ] {
+            assert_eq!(
+                parse_relative_time_at_date(now, s).unwrap(),
+                now + Duration::days(3),
+                "{s}"
+            );
+        }
+
+        for s in [
+            "in",
+            "in 3 days ago",
+            "3 days ago from now",
+            "in 3 days from now",
+            "from now",
+            "3 days from",
+        ] {
+            assert_eq!(
+                parse_relative_time_at_date(now, s),
+                Err(ParseDateTimeError::InvalidInput),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_article_as_one() {
+        assert_eq!(parse_duration("an hour").unwrap(), Duration::hours(1));
+        assert_eq!(parse_duration("a week ago").unwrap(), Duration::days(-7));
+        assert_eq!(
+            parse_duration("a day and an hour").unwrap(),
+            Duration::hours(25)
+        );
+        assert_eq!(
+            parse_duration("a minute, a second").unwrap(),
+            Duration::seconds(61)
+        );
+        assert_eq!(parse_duration("a day").unwrap(), Duration::days(1));
+        assert_eq!(parse_duration("a fortnight").unwrap(), Duration::days(14));
+        assert_eq!(parse_duration("an hour ago").unwrap(), Duration::hours(-1));
+        assert_eq!(parse_duration("A week ago").unwrap(), Duration::days(-7));
+        assert_eq!(parse_duration("An hour").unwrap(), Duration::hours(1));
+
+        // an article needs a unit
+        for s in ["a", "an", "a ago", "ahour", "an 2 hours"] {
+            assert_eq!(
+                parse_duration(s),
+                Err(ParseDateTimeError::InvalidInput),
+                "{s}"
+            );
+        }
+    }
+
     #[test]
     fn test_no_spaces() {
-        let now = Utc::now();
+        let now = mid_month_now();
         assert_eq!(parse_duration("-1hour").unwrap(), Duration::hours(-1));
         assert_eq!(parse_duration("+3days").unwrap(), Duration::days(3));
         assert_eq!(parse_duration("2weeks").unwrap(), Duration::weeks(2));
@@ -388,6 +647,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ordinal_words() {
+        let now = Utc::now();
+        for (s, expected) in [
+            ("first week", Duration::weeks(1)),
+            ("third day", Duration::days(3)),
+            ("twelfth hour", Duration::hours(12)),
+            ("twentieth week", Duration::weeks(20)),
+            ("thirty-first day ago", Duration::days(-31)),
+        ] {
+            assert_eq!(
+                parse_relative_time_at_date(now, s).unwrap(),
+                now + expected,
+                "{s}"
+            );
+        }
+        assert!(parse_relative_time_at_date(now, "thirty-second day").is_err());
+    }
+
+    #[test]
+    fn test_qualifiers() {
+        let now = Utc::now();
+        for (s, expected) in [
+            ("about 3 hours", "3 hours"),
+            ("exactly 2 days", "2 days"),
+            ("roughly a week", "a week"),
+            ("approximately  1 year ago", "1 year ago"),
+            ("About 3 hours", "3 hours"),
+            ("ABOUT 3 hours", "3 hours"),
+        ] {
+            assert_eq!(
+                parse_relative_time_at_date(now, s),
+                parse_relative_time_at_date(now, expected),
+                "{s}"
+            );
+        }
+
+        for s in [
+            "about",
+            "about about 3 hours",
+            "3 hours about",
+            "nearly 3 hours",
+        ] {
+            assert!(parse_relative_time_at_date(now, s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_times() {
+        let jan_31 = Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap();
+        assert_eq!(
+            parse_relative_time_times_at_date(jan_31, "month", 3).unwrap(),
+            Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_relative_time_times_at_date(jan_31, "2 days ago", 3).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 25, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_relative_time_times_at_date(jan_31, "tomorrow", 2).unwrap(),
+            Utc.with_ymd_and_hms(2024, 2, 2, 0, 0, 0).unwrap()
+        );
+        assert!(parse_relative_time_times_at_date(jan_31, "9223372036854775807 days", 2).is_err());
+    }
+
     #[test]
     fn test_invalid_input() {
         let result = parse_duration("foobar");
@@ -426,7 +750,7 @@ mod tests {
 
     #[test]
     fn test_direction() {
-        let now = Utc::now();
+        let now = mid_month_now();
         assert_eq!(
             parse_duration("last hour").unwrap(),
             Duration::seconds(-3600)
@@ -446,9 +770,39 @@ mod tests {
         assert_eq!(parse_duration("this year").unwrap(), Duration::days(0));
     }
 
+    #[test]
+    fn test_direction_preserves_time_of_day() {
+        let now = Utc.from_utc_datetime(&NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(),
+            NaiveTime::from_hms_opt(14, 30, 45).unwrap(),
+        ));
+        let expected_time = NaiveTime::from_hms_opt(14, 30, 45).unwrap();
+
+        let last_week = parse_relative_time_at_date(now, "last week").unwrap();
+        assert_eq!(
+            last_week.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 3, 8).unwrap()
+        );
+        assert_eq!(last_week.time(), expected_time);
+
+        let next_month = parse_relative_time_at_date(now, "next month").unwrap();
+        assert_eq!(
+            next_month.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 4, 15).unwrap()
+        );
+        assert_eq!(next_month.time(), expected_time);
+
+        let last_year = parse_relative_time_at_date(now, "last year").unwrap();
+        assert_eq!(
+            last_year.date_naive(),
+            NaiveDate::from_ymd_opt(2023, 3, 15).unwrap()
+        );
+        assert_eq!(last_year.time(), expected_time);
+    }
+
     #[test]
     fn test_duration_parsing() {
-        let now = Utc::now();
+        let now = mid_month_now();
         assert_eq!(
             parse_relative_time_at_date(now, "1 year").unwrap(),
             now.checked_add_months(Months::new(12)).unwrap()
@@ -670,4 +1024,63 @@ mod tests {
         let result = parse_relative_time_at_date(now, "invalid 1r");
         assert_eq!(result, Err(ParseDateTimeError::InvalidInput));
     }
+
+    #[test]
+    fn test_parse_std_duration() {
+        use std::time::Duration;
+
+        assert_eq!(parse_std_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(
+            parse_std_duration("1 hour 30 minutes"),
+            Ok(Duration::from_secs(5400))
+        );
+        assert_eq!(
+            parse_std_duration("2 weeks"),
+            Ok(Duration::from_secs(1_209_600))
+        );
+        assert_eq!(parse_std_duration("now"), Ok(Duration::ZERO));
+        assert_eq!(
+            parse_std_duration("90 minutes"),
+            Ok(Duration::from_secs(5400))
+        );
+        assert_eq!(
+            parse_std_duration("2 days and 1 second"),
+            Ok(Duration::from_secs(172_801))
+        );
+        assert_eq!(parse_std_duration("an hour"), Ok(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_parse_std_duration_invalid() {
+        assert_eq!(
+            parse_std_duration("1 month"),
+            Err(ParseDateTimeError::AmbiguousCalendarUnit)
+        );
+        assert_eq!(
+            parse_std_duration("1 year 2 days"),
+            Err(ParseDateTimeError::AmbiguousCalendarUnit)
+        );
+        assert_eq!(
+            parse_std_duration("-5 minutes"),
+            Err(ParseDateTimeError::NegativeDuration)
+        );
+        // a std duration is unsigned, so a time in the past has no equivalent
+        assert_eq!(
+            parse_std_duration("90 minutes ago"),
+            Err(ParseDateTimeError::NegativeDuration)
+        );
+        assert_eq!(
+            parse_std_duration("2 days ago"),
+            Err(ParseDateTimeError::NegativeDuration)
+        );
+        assert_eq!(
+            parse_std_duration("foobar"),
+            Err(ParseDateTimeError::InvalidInput)
+        );
+        // fractional units are not supported
+        assert_eq!(
+            parse_std_duration("1.5 hours"),
+            Err(ParseDateTimeError::InvalidInput)
+        );
+    }
 }
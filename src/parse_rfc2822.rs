@@ -0,0 +1,101 @@
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use chrono::{DateTime, FixedOffset, TimeZone};
+
+use crate::parse_time_only_str::to_offset;
+
+/// Parses an RFC 2822 date and time as found in the "Date:" header of an
+/// email, e.g. "Tue, 1 Feb 2022 06:37:47 +0000" or
+/// "Wed, 01 Jan 1997 00:00:00 GMT".
+///
+/// RFC 5322 asks for single letter military time zones to be read as -0000,
+/// but like gnu date they are given their nominal offset instead, the same as
+/// for a time on its own.
+pub(crate) fn parse_rfc2822(s: &str) -> Option<DateTime<FixedOffset>> {
+    let s = s.trim();
+
+    if let Some((date_time, tz)) = s.rsplit_once(' ') {
+        if let Some(offset) = to_offset(tz) {
+            let parsed = DateTime::parse_from_rfc2822(&format!("{date_time} +0000")).ok()?;
+            return offset.from_local_datetime(&parsed.naive_local()).single();
+        }
+    }
+
+    DateTime::parse_from_rfc2822(s).ok()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use chrono::{DateTime, FixedOffset, TimeZone};
+
+    use crate::parse_rfc2822::parse_rfc2822;
+
+    fn at_offset(hours: i32, s: &str) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(hours * 3600)
+            .unwrap()
+            .from_local_datetime(&s.parse().unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_valid_rfc2822() {
+        for (s, expected) in [
+            (
+                "Tue, 1 Feb 2022 06:37:47 +0000",
+                at_offset(0, "2022-02-01T06:37:47"),
+            ),
+            (
+                "Wed, 01 Jan 1997 00:00:00 GMT",
+                at_offset(0, "1997-01-01T00:00:00"),
+            ),
+            (
+                "1 Feb 2022 06:37:47 -0500",
+                at_offset(-5, "2022-02-01T06:37:47"),
+            ),
+            (
+                "Tue, 1 Feb 2022 06:37:47 EST",
+                at_offset(-5, "2022-02-01T06:37:47"),
+            ),
+            (
+                "Fri, 21 Nov 1997 09:55 -0600",
+                at_offset(-6, "1997-11-21T09:55:00"),
+            ),
+            (
+                " Tue, 1 Feb 2022 06:37:47 +0000 (UTC) ",
+                at_offset(0, "2022-02-01T06:37:47"),
+            ),
+        ] {
+            assert_eq!(parse_rfc2822(s), Some(expected), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_military_time_zones() {
+        assert_eq!(
+            parse_rfc2822("Tue, 1 Feb 2022 06:37:47 Z"),
+            Some(at_offset(0, "2022-02-01T06:37:47"))
+        );
+        assert_eq!(
+            parse_rfc2822("Tue, 1 Feb 2022 06:37:47 C"),
+            Some(at_offset(3, "2022-02-01T06:37:47"))
+        );
+        assert_eq!(
+            parse_rfc2822("Tue, 1 Feb 2022 06:37:47 N"),
+            Some(at_offset(-1, "2022-02-01T06:37:47"))
+        );
+    }
+
+    #[test]
+    fn test_invalid_rfc2822() {
+        for s in [
+            "Wed, 1 Feb 2022 06:37:47 +0000",
+            "Tue, 1 Feb 2022",
+            "Tue, 1 Foo 2022 06:37:47 +0000",
+            "Tue, 1 Feb 2022 06:37:47 J",
+            "2022-02-01 06:37:47 +0000",
+        ] {
+            assert!(parse_rfc2822(s).is_none(), "{s}");
+        }
+    }
+}
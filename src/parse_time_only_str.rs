@@ -1,17 +1,18 @@
-use chrono::{DateTime, FixedOffset, Local, NaiveTime, TimeZone};
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveTime, TimeZone};
 use regex::Regex;
 
 mod time_only_formats {
     pub const HH_MM: &str = "%R";
     pub const HH_MM_SS: &str = "%T";
     pub const TWELVEHOUR: &str = "%r";
+    pub const TWELVEHOUR_HH_MM: &str = "%I:%M %p";
 }
 
 /// Convert a military time zone string to a time zone offset.
 ///
 /// Military time zones are the letters A through Z except J. They are
 /// described in RFC 5322.
-fn to_offset(tz: &str) -> Option<FixedOffset> {
+pub(crate) fn to_offset(tz: &str) -> Option<FixedOffset> {
     let hour = match tz {
         "A" => 1,
         "B" => 2,
@@ -44,23 +45,78 @@ fn to_offset(tz: &str) -> Option<FixedOffset> {
     FixedOffset::east_opt(offset_in_sec)
 }
 
+/// Convert a time zone abbreviation such as "PST" or "CEST" to a time zone
+/// offset.
+///
+/// The table is the one used by gnu date. Only the uppercase abbreviations in
+/// the table are matched, so an ordinary trailing word such as "cat" isn't
+/// taken as a zone. Ambiguous abbreviations resolve the way gnu date does,
+/// e.g. "IST" is India Standard Time rather than Irish or Israel Standard Time.
+pub(crate) fn abbreviation_to_offset(tz: &str) -> Option<FixedOffset> {
+    let minutes = match tz {
+        "GMT" | "UT" | "UTC" | "WET" => 0,
+        "WEST" | "BST" => 60,
+        "ART" | "BRT" => -3 * 60,
+        "BRST" => -2 * 60,
+        "NST" => -(3 * 60 + 30),
+        "NDT" => -(2 * 60 + 30),
+        "AST" | "CLT" => -4 * 60,
+        "ADT" | "CLST" => -3 * 60,
+        "EST" => -5 * 60,
+        "EDT" => -4 * 60,
+        "CST" => -6 * 60,
+        "CDT" => -5 * 60,
+        "MST" => -7 * 60,
+        "MDT" => -6 * 60,
+        "PST" => -8 * 60,
+        "PDT" => -7 * 60,
+        "AKST" => -9 * 60,
+        "AKDT" => -8 * 60,
+        "HST" | "HAST" => -10 * 60,
+        "HADT" => -9 * 60,
+        "WAT" | "CET" | "MET" | "MEZ" => 60,
+        "CEST" | "MEST" | "MESZ" | "EET" | "CAT" | "SAST" => 2 * 60,
+        "EEST" | "EAT" | "MSK" => 3 * 60,
+        "MSD" => 4 * 60,
+        "IST" => 5 * 60 + 30,
+        "SGT" => 8 * 60,
+        "KST" | "JST" => 9 * 60,
+        "GST" => 10 * 60,
+        "NZST" => 12 * 60,
+        "NZDT" => 13 * 60,
+        _ => return None,
+    };
+    FixedOffset::east_opt(minutes * 60)
+}
+
+/// Convert a time keyword such as "midnight" to the time of day it stands for.
+///
+/// These are the keywords accepted by gnu date and at(1).
+pub(crate) fn keyword_to_time(s: &str) -> Option<NaiveTime> {
+    let (_, hour) = [("midnight", 0), ("noon", 12), ("teatime", 16)]
+        .into_iter()
+        .find(|(keyword, _)| keyword.eq_ignore_ascii_case(s))?;
+    NaiveTime::from_hms_opt(hour, 0, 0)
+}
+
 /// Parse a time string without an offset and apply an offset to it.
 ///
-/// Multiple formats are attempted when parsing the string.
+/// Time keywords and multiple formats are attempted when parsing the string.
 fn parse_time_with_offset_multi(
     date: DateTime<Local>,
     offset: FixedOffset,
     s: &str,
 ) -> Option<DateTime<FixedOffset>> {
-    for fmt in [
+    let formatted = [
         time_only_formats::HH_MM,
         time_only_formats::HH_MM_SS,
         time_only_formats::TWELVEHOUR,
-    ] {
-        let parsed = match NaiveTime::parse_from_str(s, fmt) {
-            Ok(t) => t,
-            Err(_) => continue,
-        };
+        time_only_formats::TWELVEHOUR_HH_MM,
+    ]
+    .into_iter()
+    .filter_map(|fmt| NaiveTime::parse_from_str(s, fmt).ok());
+
+    for parsed in keyword_to_time(s).into_iter().chain(formatted) {
         let parsed_dt = date.date_naive().and_time(parsed);
         match offset.from_local_datetime(&parsed_dt).single() {
             Some(dt) => return Some(dt),
@@ -71,34 +127,70 @@ fn parse_time_with_offset_multi(
 }
 
 pub(crate) fn parse_time_only(date: DateTime<Local>, s: &str) -> Option<DateTime<FixedOffset>> {
-    let re =
-        Regex::new(r"^(?<time>.*?)(?:(?<sign>\+|-)(?<h>[0-9]{1,2}):?(?<m>[0-9]{0,2}))?$").unwrap();
+    let re = Regex::new(
+        r"^(?<time>.*?)(?:(?<sign>[+-]+)(?:(?<h>[0-9]{1,2}):?(?<m>[0-9]{0,2}))?[+-]*)?$",
+    )
+    .unwrap();
     let captures = re.captures(s)?;
 
-    // Parse the sign, hour, and minute to get a `FixedOffset`, if possible.
-    let parsed_offset = match captures.name("h") {
-        Some(hours) if !(hours.as_str().is_empty()) => {
-            let mut offset_in_sec = hours.as_str().parse::<i32>().unwrap() * 3600;
+    // Parse the sign, hour, and minute to get an offset in seconds, if possible.
+    // Note: to stay compatible with gnu date this code allows multiple + and -
+    // and only considers the last one. A sign without any digits is a zero
+    // offset, and signs after the digits are ignored.
+    //
+    // Like gnu date, an offset has no seconds. The offset group of the regex
+    // doesn't match a seconds field, so in "12:00 +05:30:15" the offset is
+    // left as part of the time, which then fails to parse and is rejected.
+    let parsed_offset = match captures.name("sign") {
+        Some(sign) => {
+            let mut offset_in_sec = match captures.name("h") {
+                Some(hours) => hours.as_str().parse::<i32>().unwrap() * 3600,
+                None => 0,
+            };
             match captures.name("m") {
                 Some(minutes) if !(minutes.as_str().is_empty()) => {
-                    offset_in_sec += minutes.as_str().parse::<i32>().unwrap() * 60;
+                    let minutes = minutes.as_str().parse::<i32>().unwrap();
+                    // An explicit offset is not clamped, so invalid minutes
+                    // are an error rather than being carried into the hour
+                    if minutes > 59 {
+                        return None;
+                    }
+                    offset_in_sec += minutes * 60;
                 }
                 _ => (),
             };
-            offset_in_sec *= if &captures["sign"] == "-" { -1 } else { 1 };
-            FixedOffset::east_opt(offset_in_sec)
+            // gnu date accepts offsets of up to 24 hours
+            if offset_in_sec > 24 * 3600 {
+                return None;
+            }
+            offset_in_sec *= if sign.as_str().ends_with('-') { -1 } else { 1 };
+            Some(offset_in_sec)
         }
-        _ => None,
+        None => None,
     };
 
     // Parse the time and apply the parsed offset.
     let s = captures["time"].trim();
-    let offset = match parsed_offset {
-        Some(offset) => offset,
-        None => *date.offset(),
-    };
-    if let Some(result) = parse_time_with_offset_multi(date, offset, s) {
-        return Some(result);
+    match parsed_offset {
+        Some(offset_in_sec) => match FixedOffset::east_opt(offset_in_sec) {
+            Some(offset) => {
+                if let Some(result) = parse_time_with_offset_multi(date, offset, s) {
+                    return Some(result);
+                }
+            }
+            // A full day is out of range for `FixedOffset`, so the time is
+            // parsed as UTC and corrected, which moves it to another day
+            None => {
+                let result = parse_time_with_offset_multi(date, FixedOffset::east_opt(0)?, s)?
+                    - Duration::try_seconds(i64::from(offset_in_sec))?;
+                return Some(result.with_timezone(date.offset()).fixed_offset());
+            }
+        },
+        None => {
+            if let Some(result) = parse_time_with_offset_multi(date, *date.offset(), s) {
+                return Some(result);
+            }
+        }
     }
 
     // Military time zones are specified in RFC 5322, Section 4.3
@@ -107,8 +199,10 @@ pub(crate) fn parse_time_only(date: DateTime<Local>, s: &str) -> Option<DateTime
     //
     // We let the parsing above handle "5:00 AM" so at this point we
     // should be guaranteed that we don't have an AM/PM suffix. That
-    // way, we can safely parse "5:00M" here without interference.
-    let re = Regex::new(r"(?<time>.*?)(?<tz>[A-IKLMN-YZ])").unwrap();
+    // way, we can safely parse "5:00M" here without interference. The zone
+    // is a single trailing letter, so that a letter from inside an
+    // abbreviation such as "EST" isn't taken as one.
+    let re = Regex::new(r"^(?<time>.*?)(?<tz>[A-IKLMN-YZ])$").unwrap();
     let captures = re.captures(s)?;
     if let Some(tz) = captures.name("tz") {
         let s = captures["time"].trim();
@@ -126,7 +220,7 @@ pub(crate) fn parse_time_only(date: DateTime<Local>, s: &str) -> Option<DateTime
 
 #[cfg(test)]
 mod tests {
-    use crate::parse_time_only_str::parse_time_only;
+    use crate::parse_time_only_str::{abbreviation_to_offset, parse_time_only};
     use chrono::{DateTime, Local, TimeZone};
     use std::env;
 
@@ -154,6 +248,68 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_abbreviation_to_offset() {
+        for (tz, minutes) in [
+            ("GMT", 0),
+            ("UT", 0),
+            ("UTC", 0),
+            ("WET", 0),
+            ("WEST", 60),
+            ("BST", 60),
+            ("ART", -180),
+            ("BRT", -180),
+            ("BRST", -120),
+            ("NST", -210),
+            ("NDT", -150),
+            ("AST", -240),
+            ("ADT", -180),
+            ("CLT", -240),
+            ("CLST", -180),
+            ("EST", -300),
+            ("EDT", -240),
+            ("CST", -360),
+            ("CDT", -300),
+            ("MST", -420),
+            ("MDT", -360),
+            ("PST", -480),
+            ("PDT", -420),
+            ("AKST", -540),
+            ("AKDT", -480),
+            ("HST", -600),
+            ("HAST", -600),
+            ("HADT", -540),
+            ("WAT", 60),
+            ("CET", 60),
+            ("CEST", 120),
+            ("MET", 60),
+            ("MEZ", 60),
+            ("MEST", 120),
+            ("MESZ", 120),
+            ("EET", 120),
+            ("EEST", 180),
+            ("CAT", 120),
+            ("SAST", 120),
+            ("EAT", 180),
+            ("MSK", 180),
+            ("MSD", 240),
+            ("IST", 330),
+            ("SGT", 480),
+            ("KST", 540),
+            ("JST", 540),
+            ("GST", 600),
+            ("NZST", 720),
+            ("NZDT", 780),
+        ] {
+            let offset = abbreviation_to_offset(tz).unwrap();
+            assert_eq!(offset.local_minus_utc(), minutes * 60, "{tz}");
+        }
+
+        for tz in ["", "Z", "XYZ", "AEST", "PSTT", "pst", "Cat"] {
+            assert!(abbreviation_to_offset(tz).is_none(), "{tz}");
+        }
+    }
+
     #[test]
     fn test_time_with_offset() {
         env::set_var("TZ", "UTC");
@@ -181,6 +337,106 @@ mod tests {
         assert_eq!(parsed_time, 1709499840);
     }
 
+    #[test]
+    fn test_time_with_day_long_offset() {
+        env::set_var("TZ", "UTC");
+        let date = get_test_date();
+
+        // a correction of a full day moves the time to the previous or next day
+        let parsed_time = parse_time_only(date, "11:34:56+24:00").unwrap();
+        assert_eq!(parsed_time.timestamp(), 1709379296);
+        let parsed_time = parse_time_only(date, "11:34:56-24").unwrap();
+        assert_eq!(parsed_time.timestamp(), 1709552096);
+
+        let parsed_time = parse_time_only(date, "11:34:56+13:00").unwrap();
+        assert_eq!(parsed_time.timestamp(), 1709418896);
+
+        for s in ["11:34:56+24:01", "11:34:56+25", "11:34:56-99:00"] {
+            assert!(parse_time_only(date, s).is_none(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_time_with_offset_seconds() {
+        env::set_var("TZ", "UTC");
+        // gnu date rejects seconds in a numeric offset
+        for s in [
+            "12:00 +05:30:15",
+            "12:00 +053015",
+            "23:59:59+00:00:00",
+            "12:00 -05:30:00",
+        ] {
+            assert!(parse_time_only(get_test_date(), s).is_none(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_time_with_invalid_offset_minutes() {
+        env::set_var("TZ", "UTC");
+        assert!(parse_time_only(get_test_date(), "12:00 +05:75").is_none());
+        assert!(parse_time_only(get_test_date(), "12:00 +0575").is_none());
+        assert!(parse_time_only(get_test_date(), "12:00 -05:60").is_none());
+
+        let parsed_time = parse_time_only(get_test_date(), "12:00 +05:59")
+            .unwrap()
+            .timestamp();
+        assert_eq!(parsed_time, 1709445660);
+    }
+
+    #[test]
+    fn test_time_keywords() {
+        env::set_var("TZ", "UTC");
+        let parsed_time = parse_time_only(get_test_date(), "midnight")
+            .unwrap()
+            .timestamp();
+        assert_eq!(parsed_time, 1709424000);
+
+        let parsed_time = parse_time_only(get_test_date(), "Midnight +0100")
+            .unwrap()
+            .timestamp();
+        assert_eq!(parsed_time, 1709420400);
+
+        let parsed_time = parse_time_only(get_test_date(), "noon")
+            .unwrap()
+            .timestamp();
+        assert_eq!(parsed_time, 1709467200);
+
+        let parsed_time = parse_time_only(get_test_date(), "TeaTime")
+            .unwrap()
+            .timestamp();
+        assert_eq!(parsed_time, 1709481600);
+
+        assert!(parse_time_only(get_test_date(), "noonish").is_none());
+    }
+
+    #[test]
+    fn test_time_with_bare_sign_offset() {
+        env::set_var("TZ", "UTC");
+        let date = get_test_date();
+
+        // gnu date treats a sign without digits as a zero offset and only
+        // considers the last of multiple signs
+        for s in ["12:34:56+", "12:34:56-", "12:34:56+-", "12:34:56+-+++---++"] {
+            let parsed = parse_time_only(date, s).unwrap();
+            assert_eq!(parsed.offset().local_minus_utc(), 0, "{s}");
+            assert_eq!(parsed.timestamp(), 1709469296, "{s}");
+        }
+
+        let parsed = parse_time_only(date, "12:34:56+-01").unwrap();
+        assert_eq!(parsed.offset().local_minus_utc(), -3600);
+        assert_eq!(parsed.timestamp(), 1709472896);
+
+        let parsed = parse_time_only(date, "12:34:56-+01").unwrap();
+        assert_eq!(parsed.offset().local_minus_utc(), 3600);
+
+        // signs after the digits of the offset are ignored
+        for s in ["12:34:56+1-", "12:34:56+--+1-+-"] {
+            let parsed = parse_time_only(date, s).unwrap();
+            assert_eq!(parsed.offset().local_minus_utc(), 3600, "{s}");
+            assert_eq!(parsed.timestamp(), 1709465696, "{s}");
+        }
+    }
+
     #[test]
     fn test_time_with_seconds() {
         env::set_var("TZ", "UTC");
@@ -207,4 +463,23 @@ mod tests {
             .timestamp();
         assert_eq!(parsed_time, 1709499840)
     }
+
+    #[test]
+    fn test_military_zone_is_a_single_trailing_letter() {
+        env::set_var("TZ", "UTC");
+        for s in ["10:00 EST", "10:00 PST", "14:00 CET", "10:00 ZZ"] {
+            assert!(parse_time_only(get_test_date(), s).is_none(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_twelve_hour_time_without_seconds() {
+        env::set_var("TZ", "UTC");
+        let parsed = parse_time_only(get_test_date(), "9:04 PM").unwrap();
+        assert_eq!(parsed.timestamp(), 1709499840);
+        assert_eq!(parsed.offset().local_minus_utc(), 0);
+
+        let parsed = parse_time_only(get_test_date(), "8:30 pm -0500").unwrap();
+        assert_eq!(parsed.format("%H:%M %z").to_string(), "20:30 -0500");
+    }
 }
@@ -6,10 +6,12 @@ use std::fmt::Display;
 use std::num::ParseIntError;
 
 use nom::branch::alt;
-use nom::character::complete::{char, digit1};
-use nom::combinator::all_consuming;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::{char, digit1, multispace0, one_of};
+use nom::combinator::{all_consuming, opt, value};
 use nom::multi::fold_many0;
 use nom::sequence::preceded;
+use nom::sequence::terminated;
 use nom::sequence::tuple;
 use nom::{self, IResult};
 
@@ -49,36 +51,83 @@ impl<'a> From<NomError<'a>> for ParseTimestampError {
     }
 }
 
-pub(crate) fn parse_timestamp(s: &str) -> Result<i64, ParseTimestampError> {
-    let s = s.trim().to_lowercase();
-    let s = s.as_str();
-
-    let res: IResult<&str, (char, &str)> = all_consuming(preceded(
-        char('@'),
+/// The sign, integer digits, fraction digits and units per second of a
+/// timestamp.
+type TimestampParts<'a> = (char, &'a str, Option<&'a str>, Option<i64>);
+
+/// Parses an epoch timestamp such as `@1234` or `@-1.5` into seconds and
+/// nanoseconds.
+///
+/// Like gnu date, a fractional timestamp is truncated toward minus infinity,
+/// so the nanoseconds are always non-negative, e.g. `@-1.5` is `(-2, 500000000)`.
+///
+/// An integer timestamp can also be given in milliseconds, microseconds or
+/// nanoseconds with an `ms`, `us` or `ns` suffix, e.g. `@1690466034123ms`.
+/// These are truncated the same way.
+pub(crate) fn parse_timestamp(s: &str) -> Result<(i64, u32), ParseTimestampError> {
+    let s = s.trim();
+
+    let res: IResult<&str, TimestampParts> = all_consuming(preceded(
+        // gnu date allows whitespace after the @ and after each sign
+        terminated(char('@'), multispace0),
         tuple((
             // Note: to stay compatible with gnu date this code allows
             // multiple + and - and only considers the last one
             fold_many0(
                 // parse either + or -
-                alt((char('+'), char('-'))),
+                terminated(alt((char('+'), char('-'))), multispace0),
                 // start with a +
                 || '+',
                 // whatever we get (+ or -), update the accumulator to that value
                 |_, c| c,
             ),
             digit1,
+            // gnu date accepts both a period and a comma as decimal separator
+            opt(preceded(one_of(".,"), digit1)),
+            // the number of units per second
+            opt(alt((
+                value(1_000, tag_no_case("ms")),
+                value(1_000_000, tag_no_case("us")),
+                value(1_000_000_000, tag_no_case("ns")),
+            ))),
         )),
     ))(s);
 
-    let (_, (sign, number_str)) = res?;
+    let (_, (sign, number_str, fraction_str, per_second)) = res?;
 
     let mut number = number_str.parse::<i64>()?;
 
+    if let Some(per_second) = per_second {
+        // A fraction of a sub-second unit is not supported
+        if fraction_str.is_some() {
+            return Err(ParseTimestampError::InvalidInput);
+        }
+        if sign == '-' {
+            number *= -1;
+        }
+        let nanos = number.rem_euclid(per_second) * (1_000_000_000 / per_second);
+        return Ok((number.div_euclid(per_second), nanos as u32));
+    }
+    let mut nanos = fraction_str.map_or(0, parse_nanos);
+
     if sign == '-' {
         number *= -1;
+        if nanos > 0 {
+            number -= 1;
+            nanos = 1_000_000_000 - nanos;
+        }
     }
 
-    Ok(number)
+    Ok((number, nanos))
+}
+
+// Digits beyond nanosecond precision are truncated
+fn parse_nanos(digits: &str) -> u32 {
+    digits
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(9)
+        .fold(0, |acc, digit| acc * 10 + u32::from(digit - b'0'))
 }
 
 #[cfg(test)]
@@ -88,18 +137,61 @@ mod tests {
 
     #[test]
     fn test_valid_timestamp() {
-        assert_eq!(parse_timestamp("@1234"), Ok(1234));
-        assert_eq!(parse_timestamp("@99999"), Ok(99999));
-        assert_eq!(parse_timestamp("@-4"), Ok(-4));
-        assert_eq!(parse_timestamp("@-99999"), Ok(-99999));
-        assert_eq!(parse_timestamp("@+4"), Ok(4));
-        assert_eq!(parse_timestamp("@0"), Ok(0));
+        assert_eq!(parse_timestamp("@1234"), Ok((1234, 0)));
+        assert_eq!(parse_timestamp("@99999"), Ok((99999, 0)));
+        assert_eq!(parse_timestamp("@-4"), Ok((-4, 0)));
+        assert_eq!(parse_timestamp("@-99999"), Ok((-99999, 0)));
+        assert_eq!(parse_timestamp("@+4"), Ok((4, 0)));
+        assert_eq!(parse_timestamp("@0"), Ok((0, 0)));
 
         // gnu date accepts numbers signs and uses the last sign
-        assert_eq!(parse_timestamp("@---+12"), Ok(12));
-        assert_eq!(parse_timestamp("@+++-12"), Ok(-12));
-        assert_eq!(parse_timestamp("@+----+12"), Ok(12));
-        assert_eq!(parse_timestamp("@++++-123"), Ok(-123));
+        assert_eq!(parse_timestamp("@---+12"), Ok((12, 0)));
+        assert_eq!(parse_timestamp("@+++-12"), Ok((-12, 0)));
+        assert_eq!(parse_timestamp("@+----+12"), Ok((12, 0)));
+        assert_eq!(parse_timestamp("@++++-123"), Ok((-123, 0)));
+    }
+
+    #[test]
+    fn test_timestamp_with_whitespace() {
+        assert_eq!(parse_timestamp("@ 1234"), Ok((1234, 0)));
+        assert_eq!(parse_timestamp("@ - 1234"), Ok((-1234, 0)));
+        assert_eq!(parse_timestamp("@+ - 1234"), Ok((-1234, 0)));
+        assert_eq!(parse_timestamp(" @1234 "), Ok((1234, 0)));
+        assert_eq!(parse_timestamp("@\t-1.5"), Ok((-2, 500_000_000)));
+    }
+
+    #[test]
+    fn test_fractional_timestamp() {
+        assert_eq!(parse_timestamp("@1.5"), Ok((1, 500_000_000)));
+        assert_eq!(parse_timestamp("@1,5"), Ok((1, 500_000_000)));
+        assert_eq!(parse_timestamp("@0.000000001"), Ok((0, 1)));
+        assert_eq!(parse_timestamp("@0.1234567891"), Ok((0, 123_456_789)));
+
+        // negative values are truncated toward minus infinity
+        assert_eq!(parse_timestamp("@-1.5"), Ok((-2, 500_000_000)));
+        assert_eq!(parse_timestamp("@-0.25"), Ok((-1, 750_000_000)));
+        assert_eq!(parse_timestamp("@-3.0"), Ok((-3, 0)));
+    }
+
+    #[test]
+    fn test_sub_second_unit_timestamp() {
+        assert_eq!(
+            parse_timestamp("@1690466034123ms"),
+            Ok((1690466034, 123_000_000))
+        );
+        assert_eq!(
+            parse_timestamp("@1690466034.123"),
+            Ok((1690466034, 123_000_000))
+        );
+        assert_eq!(parse_timestamp("@1500000us"), Ok((1, 500_000_000)));
+        assert_eq!(parse_timestamp("@1500000001ns"), Ok((1, 500_000_001)));
+        assert_eq!(parse_timestamp("@0ms"), Ok((0, 0)));
+        assert_eq!(parse_timestamp("@ 1500MS"), Ok((1, 500_000_000)));
+
+        // negative values are truncated toward minus infinity
+        assert_eq!(parse_timestamp("@-1500ms"), Ok((-2, 500_000_000)));
+        assert_eq!(parse_timestamp("@-1ns"), Ok((-1, 999_999_999)));
+        assert_eq!(parse_timestamp("@-3000ms"), Ok((-3, 0)));
     }
 
     #[test]
@@ -107,5 +199,15 @@ mod tests {
         assert!(parse_timestamp("@").is_err());
         assert!(parse_timestamp("@+--+").is_err());
         assert!(parse_timestamp("@+1ab2").is_err());
+        assert!(parse_timestamp("@1.").is_err());
+        assert!(parse_timestamp("@.5").is_err());
+        assert!(parse_timestamp("@12 34").is_err());
+        assert!(parse_timestamp("@12 .5").is_err());
+        assert!(parse_timestamp(" 1234").is_err());
+        assert!(parse_timestamp("1234ms").is_err());
+        assert!(parse_timestamp("@1.5ms").is_err());
+        assert!(parse_timestamp("@1234 ms").is_err());
+        assert!(parse_timestamp("@1234s").is_err());
+        assert!(parse_timestamp("@1234msms").is_err());
     }
 }
@@ -2,22 +2,23 @@
 // file that was distributed with this source code.
 use chrono::Weekday;
 use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::combinator::value;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::{digit1, multispace1};
+use nom::combinator::{all_consuming, map_res, opt, value};
+use nom::sequence::{preceded, terminated, tuple};
 use nom::{self, IResult};
 
+use crate::parse_ordinal::ordinal;
+
 // Helper macro to simplify tag matching
 macro_rules! tag_match {
     ($day:expr, $($pattern:expr),+) => {
-        value($day, alt(($(tag($pattern)),+)))
+        value($day, alt(($(tag_no_case($pattern)),+)))
     };
 }
 
-pub(crate) fn parse_weekday(s: &str) -> Option<Weekday> {
-    let s = s.trim().to_lowercase();
-    let s = s.as_str();
-
-    let parse_result: IResult<&str, Weekday> = nom::combinator::all_consuming(alt((
+fn weekday(s: &str) -> IResult<&str, Weekday> {
+    alt((
         tag_match!(Weekday::Mon, "monday", "mon"),
         tag_match!(Weekday::Tue, "tuesday", "tues", "tue"),
         tag_match!(Weekday::Wed, "wednesday", "wednes", "wed"),
@@ -25,10 +26,42 @@ pub(crate) fn parse_weekday(s: &str) -> Option<Weekday> {
         tag_match!(Weekday::Fri, "friday", "fri"),
         tag_match!(Weekday::Sat, "saturday", "sat"),
         tag_match!(Weekday::Sun, "sunday", "sun"),
+    ))(s)
+}
+
+/// Returns whether a single word is a weekday, such as "fri" or "fridays".
+pub(crate) fn is_weekday(word: &str) -> bool {
+    all_consuming(terminated(weekday, opt(tag_no_case("s"))))(word).is_ok()
+}
+
+/// Parses a weekday with an optional "next", "last", "this", ordinal word
+/// such as "third" or count such as "2" in front of it and returns the ordinal
+/// along with the weekday: `1` for "next", `-1` for "last", `0` for "this" and
+/// `None` for a plain weekday.
+///
+/// A trailing "ago" negates the ordinal, so "2 fridays ago" is `-2`, and the
+/// weekday may be plural, as in "3 fridays".
+pub(crate) fn parse_relative_weekday(s: &str) -> Option<(Option<i64>, Weekday)> {
+    let s = s.trim();
+
+    let parse_result: IResult<&str, (Option<i64>, Weekday, Option<&str>)> = all_consuming(tuple((
+        opt(terminated(
+            alt((
+                value(1, tag_no_case("next")),
+                value(-1, tag_no_case("last")),
+                value(0, tag_no_case("this")),
+                ordinal,
+                map_res(digit1, str::parse),
+            )),
+            multispace1,
+        )),
+        terminated(weekday, opt(tag_no_case("s"))),
+        opt(preceded(multispace1, tag_no_case("ago"))),
     )))(s);
 
     match parse_result {
-        Ok((_, weekday)) => Some(weekday),
+        Ok((_, (ordinal, weekday, None))) => Some((ordinal, weekday)),
+        Ok((_, (ordinal, weekday, Some(_)))) => Some((Some(-ordinal.unwrap_or(0)), weekday)),
         Err(_) => None,
     }
 }
@@ -38,7 +71,7 @@ mod tests {
 
     use chrono::Weekday::*;
 
-    use crate::parse_weekday::parse_weekday;
+    use crate::parse_weekday::{is_weekday, parse_relative_weekday};
 
     #[test]
     fn test_valid_weekdays() {
@@ -62,10 +95,19 @@ mod tests {
         ];
 
         for (name, weekday) in days {
-            assert_eq!(parse_weekday(name), Some(weekday));
-            assert_eq!(parse_weekday(&format!(" {}", name)), Some(weekday));
-            assert_eq!(parse_weekday(&format!(" {} ", name)), Some(weekday));
-            assert_eq!(parse_weekday(&format!("{} ", name)), Some(weekday));
+            assert_eq!(parse_relative_weekday(name), Some((None, weekday)));
+            assert_eq!(
+                parse_relative_weekday(&format!(" {}", name)),
+                Some((None, weekday))
+            );
+            assert_eq!(
+                parse_relative_weekday(&format!(" {} ", name)),
+                Some((None, weekday))
+            );
+            assert_eq!(
+                parse_relative_weekday(&format!("{} ", name)),
+                Some((None, weekday))
+            );
 
             let (left, right) = name.split_at(1);
             let (test_str1, test_str2) = (
@@ -73,8 +115,8 @@ mod tests {
                 format!("{}{}", left.to_lowercase(), right.to_uppercase()),
             );
 
-            assert_eq!(parse_weekday(&test_str1), Some(weekday));
-            assert_eq!(parse_weekday(&test_str2), Some(weekday));
+            assert_eq!(parse_relative_weekday(&test_str1), Some((None, weekday)));
+            assert_eq!(parse_relative_weekday(&test_str2), Some((None, weekday)));
         }
     }
 
@@ -93,7 +135,65 @@ mod tests {
             "yesterday",
         ];
         for day in days {
-            assert!(parse_weekday(day).is_none());
+            assert!(parse_relative_weekday(day).is_none());
+        }
+    }
+
+    #[test]
+    fn test_is_weekday() {
+        for word in ["mon", "Friday", "fridays", "WEDS"] {
+            assert!(is_weekday(word), "{word}");
+        }
+        for word in ["", "monday 14:00", "next monday", "mond", "noon"] {
+            assert!(!is_weekday(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn test_relative_weekdays() {
+        assert_eq!(parse_relative_weekday("monday"), Some((None, Mon)));
+        assert_eq!(parse_relative_weekday("this fri"), Some((Some(0), Fri)));
+        assert_eq!(parse_relative_weekday("next monday"), Some((Some(1), Mon)));
+        assert_eq!(
+            parse_relative_weekday(" Last  Sunday "),
+            Some((Some(-1), Sun))
+        );
+        assert_eq!(parse_relative_weekday("first fri"), Some((Some(1), Fri)));
+        assert_eq!(
+            parse_relative_weekday("third tuesday"),
+            Some((Some(3), Tue))
+        );
+        assert_eq!(
+            parse_relative_weekday(" Thirteenth  Tuesday "),
+            Some((Some(13), Tue))
+        );
+        assert_eq!(parse_relative_weekday("2 wed"), Some((Some(2), Wed)));
+        assert_eq!(parse_relative_weekday("3 fridays"), Some((Some(3), Fri)));
+        assert_eq!(parse_relative_weekday("3 FRIDAYS"), Some((Some(3), Fri)));
+        assert_eq!(parse_relative_weekday("2 wed ago"), Some((Some(-2), Wed)));
+        assert_eq!(
+            parse_relative_weekday("this friday AGO"),
+            Some((Some(0), Fri))
+        );
+        assert_eq!(
+            parse_relative_weekday("last monday ago"),
+            Some((Some(1), Mon))
+        );
+
+        for s in [
+            "next",
+            "nextmonday",
+            "next mond",
+            "second tuesday",
+            "thirteenth",
+            "thirteenthtuesday",
+            "3rd tuesday",
+            "-2 wed",
+            "2 wed agoo",
+            "2 wed ago ago",
+            "ago",
+        ] {
+            assert!(parse_relative_weekday(s).is_none());
         }
     }
 }
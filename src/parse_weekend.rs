@@ -0,0 +1,89 @@
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use chrono::Weekday;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::multispace1;
+use nom::combinator::{all_consuming, value};
+use nom::sequence::separated_pair;
+use nom::{self, IResult};
+
+/// Parses "next weekend", "last weekend" or "this weekend" and returns the
+/// direction: `1` for next, `-1` for last and `0` for this.
+pub(crate) fn parse_weekend(s: &str) -> Option<i64> {
+    let s = s.trim();
+
+    let parse_result: IResult<&str, (i64, &str)> = all_consuming(separated_pair(
+        alt((
+            value(1, tag_no_case("next")),
+            value(-1, tag_no_case("last")),
+            value(0, tag_no_case("this")),
+        )),
+        multispace1,
+        tag_no_case("weekend"),
+    ))(s);
+
+    match parse_result {
+        Ok((_, (direction, _))) => Some(direction),
+        Err(_) => None,
+    }
+}
+
+/// Returns the number of days from `weekday` to the start of the weekend,
+/// which is Saturday, in the given direction.
+///
+/// "next" is the first Saturday after the base date and "last" the most recent
+/// Saturday before it. "this" is the Saturday of the current week, where weeks
+/// start on Monday, so it is in the past on a Sunday.
+pub(crate) fn days_to_weekend(direction: i64, weekday: Weekday) -> i64 {
+    let day = i64::from(weekday.num_days_from_monday());
+    let saturday = i64::from(Weekday::Sat.num_days_from_monday());
+    match direction {
+        1 => (saturday - day - 1).rem_euclid(7) + 1,
+        -1 => -((day - saturday - 1).rem_euclid(7) + 1),
+        _ => saturday - day,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use chrono::Weekday::*;
+
+    use crate::parse_weekend::{days_to_weekend, parse_weekend};
+
+    #[test]
+    fn test_valid_weekend() {
+        assert_eq!(parse_weekend("next weekend"), Some(1));
+        assert_eq!(parse_weekend("last weekend"), Some(-1));
+        assert_eq!(parse_weekend("this weekend"), Some(0));
+        assert_eq!(parse_weekend(" Next  Weekend "), Some(1));
+    }
+
+    #[test]
+    fn test_invalid_weekend() {
+        for s in [
+            "weekend",
+            "next weekends",
+            "nextweekend",
+            "previous weekend",
+        ] {
+            assert!(parse_weekend(s).is_none());
+        }
+    }
+
+    #[test]
+    fn test_days_to_weekend() {
+        assert_eq!(days_to_weekend(1, Wed), 3);
+        assert_eq!(days_to_weekend(-1, Wed), -4);
+        assert_eq!(days_to_weekend(0, Wed), 3);
+
+        assert_eq!(days_to_weekend(1, Sat), 7);
+        assert_eq!(days_to_weekend(-1, Sat), -7);
+        assert_eq!(days_to_weekend(0, Sat), 0);
+
+        assert_eq!(days_to_weekend(1, Sun), 6);
+        assert_eq!(days_to_weekend(-1, Sun), -1);
+        assert_eq!(days_to_weekend(0, Sun), -1);
+    }
+}